@@ -1,15 +1,61 @@
 use crate::args_parsing::*;
-use crate::base_types::{ApproxName, BaseType, FieldFormatted};
+use crate::base_types::{
+    get_cast_conversion, get_map_value_type, ApproxName, ApproxTrait, BaseType, CustomComparison,
+    FieldFormatted,
+};
 use crate::AbsDiffEqParser;
 
 impl syn::parse::Parse for AbsDiffEqParser {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        let base_type: BaseType = input.parse()?;
+        let mut base_type: BaseType = input.parse()?;
         let struct_args = StructArgs::from_attrs(base_type.attrs())?;
-        Ok(Self {
+        // Special values are treated equally for all fields if specified for the object
+        for field_with_args in base_type.fields_mut() {
+            let args = &mut field_with_args.args;
+            args.nan_equal = args.nan_equal.or(struct_args.nan_equal);
+            args.inf_equal = args.inf_equal.or(struct_args.inf_equal);
+        }
+        // The tolerance of a field can not be derived from the field itself
+        if let BaseType::Struct {
+            fields_with_args, ..
+        } = &base_type
+        {
+            for (n, field_with_args) in fields_with_args.iter().enumerate() {
+                field_with_args.check_tolerance_references(n)?;
+            }
+        }
+        if let (Some(rhs), BaseType::Enum { .. }) = (&struct_args.rhs, &base_type) {
+            use syn::spanned::Spanned;
+            return Err(syn::Error::new(
+                rhs.span(),
+                "comparing against a different type with rhs is only supported for structs",
+            ));
+        }
+        if struct_args.epsilon_struct.unwrap_or(false) {
+            use syn::spanned::Spanned;
+            if let BaseType::Enum { item_enum, .. } = &base_type {
+                return Err(syn::Error::new(
+                    item_enum.ident.span(),
+                    "epsilon_struct is only supported for structs",
+                ));
+            }
+            if !base_type.generics().params.is_empty() {
+                return Err(syn::Error::new(
+                    base_type.generics().span(),
+                    "epsilon_struct is not supported for generic structs",
+                ));
+            }
+        }
+        let parser = Self {
             base_type,
             struct_args,
-        })
+        };
+        parser.check_profiles()?;
+        parser.check_computed_properties()?;
+        parser.check_remote()?;
+        parser.check_transparent()?;
+        parser.check_via()?;
+        Ok(parser)
     }
 }
 
@@ -37,7 +83,11 @@ impl AbsDiffEqParser {
                         .find(|f| f.args.skip.is_none_or(|x| !x)),
                 }
                 .map(|field| {
-                    let field_type = &field.ty;
+                    // Maps compared by key use the tolerance of their values
+                    let field_type = match field.args.use_map_keys {
+                        Some(true) => get_map_value_type(&field.ty).unwrap_or(&field.ty),
+                        _ => &field.ty,
+                    };
                     quote::quote!(#field_type)
                 })
             })
@@ -53,6 +103,13 @@ impl AbsDiffEqParser {
     pub fn get_epsilon_type_and_default_value(
         &self,
     ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        if self.uses_epsilon_struct() {
+            let epsilon_struct = self.get_epsilon_struct_ident();
+            return (
+                quote::quote!(#epsilon_struct),
+                quote::quote!(<#epsilon_struct as Default>::default()),
+            );
+        }
         let parent = self.get_epsilon_parent_type();
         let epsilon_type = self.get_derived_epsilon_type();
         let epsilon_default_value = self
@@ -65,16 +122,36 @@ impl AbsDiffEqParser {
         (epsilon_type, epsilon_default_value)
     }
 
-    pub fn generics_involved(&self) -> bool {
-        let parent = self.get_epsilon_parent_type();
-        self.base_type
-            .generics()
-            .params
-            .iter()
-            .any(|param| quote::quote!(#param).to_string() == parent.to_string())
+    /// The type which is used as the `Rhs` parameter of the derived traits
+    pub fn get_rhs_type(&self) -> proc_macro2::TokenStream {
+        match &self.struct_args.rhs {
+            Some(rhs) => quote::quote!(#rhs),
+            None => quote::quote!(Self),
+        }
+    }
+
+    /// Generic arguments of the derived traits (empty when comparing against `Self`)
+    pub fn get_trait_rhs(&self) -> proc_macro2::TokenStream {
+        match &self.struct_args.rhs {
+            Some(rhs) => quote::quote!(<#rhs>),
+            None => quote::quote!(),
+        }
+    }
+
+    /// Generic arguments of the traits used to compare individual fields.
+    /// When comparing against a different type, we let the compiler infer the type of the other
+    /// field.
+    pub fn get_field_trait_rhs(&self) -> proc_macro2::TokenStream {
+        match &self.struct_args.rhs {
+            Some(_) => quote::quote!(<_>),
+            None => quote::quote!(),
+        }
     }
 
     pub fn get_max_relative_default_value(&self) -> proc_macro2::TokenStream {
+        if self.uses_epsilon_struct() {
+            return self.get_epsilon_struct_default_value(true);
+        }
         let epsilon_type = self.get_epsilon_parent_type();
         self.struct_args
             .default_max_relative_value
@@ -86,6 +163,18 @@ impl AbsDiffEqParser {
             .unwrap()
     }
 
+    pub fn get_max_ulps_default_value(&self) -> proc_macro2::TokenStream {
+        let epsilon_type = self.get_epsilon_parent_type();
+        self.struct_args
+            .default_max_ulps_value
+            .clone()
+            .map(|x| quote::quote!(#x))
+            .or_else(|| {
+                Some(quote::quote!(<#epsilon_type as #ApproxName::UlpsEq>::default_max_ulps()))
+            })
+            .unwrap()
+    }
+
     pub fn format_nth_field(
         &self,
         n: usize,
@@ -101,17 +190,26 @@ impl AbsDiffEqParser {
         let parent_type = self.get_epsilon_parent_type();
 
         // Save field name and type in variables for easy access
-        use core::str::FromStr;
+        // The fields of other are spanned such that errors point to the field in question if the
+        // type specified by rhs does not contain it.
+        use syn::spanned::Spanned;
         let (field_name1, field_name2) = match (&field_with_args.ident, idents) {
-            (Some(id), None) => (quote::quote!(self.#id), quote::quote!(other.#id)),
+            (Some(id), None) => (
+                quote::quote!(self.#id),
+                quote::quote_spanned!(id.span()=> other.#id),
+            ),
             (None, None) => {
-                let field_number = proc_macro2::TokenStream::from_str(&format!("{}", n)).unwrap();
+                let field_number = syn::Index {
+                    index: n as u32,
+                    span: field_with_args.ty.span(),
+                };
                 (
                     quote::quote!(self.#field_number),
-                    quote::quote!(other.#field_number),
+                    quote::quote_spanned!(field_with_args.ty.span()=> other.#field_number),
                 )
             }
-            (_, Some((id1, id2))) => (quote::quote!(#id1), quote::quote!(#id2)),
+            // Enum variants bind references to their fields
+            (_, Some((id1, id2))) => (quote::quote!((*#id1)), quote::quote!((*#id2))),
         };
         let field_type = &field_with_args.ty;
 
@@ -119,39 +217,82 @@ impl AbsDiffEqParser {
         let cast_strategy = &field_with_args.args.cast_strategy;
 
         // Get static values (if present) for epsilon and max_relative
-        let epsilon = &field_with_args
-            .args
-            .epsilon_static_value
-            .clone()
-            .map(|x| quote::quote!(#x))
-            .or_else(|| Some(quote::quote!(epsilon)))
-            .unwrap();
-        let max_relative = field_with_args
+        // When using a companion epsilon struct, the static values only determine its default
+        // value and the tolerances are taken from the corresponding member.
+        let epsilon_struct_member = self.get_epsilon_struct_member(n, field_with_args);
+        let (epsilon, max_relative) = match &epsilon_struct_member {
+            Some(member) => (
+                quote::quote!(epsilon.#member),
+                quote::quote!(max_relative.#member),
+            ),
+            None => (
+                field_with_args
+                    .args
+                    .epsilon_static_value
+                    .clone()
+                    .map(|x| quote::quote!(#x))
+                    .or_else(|| Some(quote::quote!(epsilon)))
+                    .unwrap(),
+                field_with_args
+                    .args
+                    .max_relative_static_value
+                    .clone()
+                    .map(|x| quote::quote!(#x))
+                    .or_else(|| Some(quote::quote!(max_relative)))
+                    .unwrap(),
+            ),
+        };
+        // Tolerances given by expressions may depend on both compared objects
+        let (epsilon, max_relative) = match (
+            &field_with_args.args.epsilon_value,
+            &field_with_args.args.max_relative_value,
+        ) {
+            (Some(eps), Some(max_rel)) => (quote::quote!((#eps)), quote::quote!((#max_rel))),
+            (Some(eps), None) => (quote::quote!((#eps)), max_relative),
+            (None, Some(max_rel)) => (epsilon, quote::quote!((#max_rel))),
+            (None, None) => (epsilon, max_relative),
+        };
+        let mut max_ulps = field_with_args
             .args
-            .max_relative_static_value
+            .max_ulps_static_value
             .clone()
             .map(|x| quote::quote!(#x))
-            .or_else(|| Some(quote::quote!(max_relative)))
+            .or_else(|| Some(quote::quote!(max_ulps)))
             .unwrap();
 
         // Use the casting strategy
         let (base_type, own_field, other_field, mut epsilon, mut max_relative) = match cast_strategy
         {
-            Some(TypeCast::CastField) => (
-                quote::quote!(#parent_type),
-                quote::quote!(&(#field_name1.clone() as #parent_type)),
-                quote::quote!(&(#field_name2.clone() as #parent_type)),
-                quote::quote!(#epsilon.clone()),
-                quote::quote!(#max_relative.clone()),
-            ),
-            Some(TypeCast::CastValue) => (
-                quote::quote!(#field_type),
-                quote::quote!(&#field_name1),
-                quote::quote!(&#field_name2),
-                quote::quote!((#epsilon.clone() as #field_type)),
-                quote::quote!((#max_relative.clone() as #field_type)),
-            ),
-            None => (
+            Some(TypeCast::CastField(options)) => {
+                let target = match &options.to {
+                    Some(to) => quote::quote!(#to),
+                    None => parent_type,
+                };
+                let own_field = get_cast_conversion(field_name1.clone(), options.method, &target);
+                let other_field = get_cast_conversion(field_name2.clone(), options.method, &target);
+                (
+                    target,
+                    quote::quote!(&#own_field),
+                    quote::quote!(&#other_field),
+                    quote::quote!(#epsilon.clone()),
+                    quote::quote!(#max_relative.clone()),
+                )
+            }
+            // The tolerances of a companion epsilon struct already have the correct type
+            Some(TypeCast::CastValue(options)) if epsilon_struct_member.is_none() => {
+                let target = match &options.to {
+                    Some(to) => quote::quote!(#to),
+                    None => quote::quote!(#field_type),
+                };
+                (
+                    quote::quote!(#field_type),
+                    quote::quote!(&#field_name1),
+                    quote::quote!(&#field_name2),
+                    get_cast_conversion(epsilon, options.method, &target),
+                    get_cast_conversion(max_relative, options.method, &target),
+                )
+            }
+            _ => (
                 quote::quote!(#field_type),
                 quote::quote!(&#field_name1),
                 quote::quote!(&#field_name2),
@@ -159,18 +300,43 @@ impl AbsDiffEqParser {
                 quote::quote!(#max_relative.clone()),
             ),
         };
+        let fallible_cast = cast_strategy
+            .as_ref()
+            .filter(|cast| cast.options().method == CastMethod::TryInto)
+            .map(|_| (quote::quote!(&#field_name1), quote::quote!(&#field_name2)));
         if let Some(eps_map) = &field_with_args.args.epsilon_mapping {
             epsilon = quote::quote!((#eps_map)(#epsilon));
         };
         if let Some(max_rel_map) = &field_with_args.args.max_relative_mapping {
             max_relative = quote::quote!((#max_rel_map)(#max_relative));
         };
+        if let Some(max_ulps_map) = &field_with_args.args.max_ulps_mapping {
+            max_ulps = quote::quote!((#max_ulps_map)(#max_ulps));
+        };
 
         let mapping = field_with_args
             .args
             .mapping
             .clone()
             .map(|expr| quote::quote!(#expr));
+        let compare_with = match (
+            &field_with_args.args.with_module,
+            &field_with_args.args.compare_with,
+        ) {
+            (Some(module), _) => Some(CustomComparison::Module(quote::quote!(#module))),
+            (_, Some(function)) => Some(CustomComparison::Function(quote::quote!(#function))),
+            _ => None,
+        };
+
+        // Fields which are skipped conditionally are considered equal
+        let skip_condition = match (
+            &field_with_args.args.skip_if,
+            &field_with_args.args.compare_if,
+        ) {
+            (Some(condition), _) => Some(quote::quote!((#condition))),
+            (_, Some(condition)) => Some(quote::quote!(!(#condition))),
+            _ => None,
+        };
 
         // Return the fully formatted field
         Some(FieldFormatted {
@@ -179,9 +345,14 @@ impl AbsDiffEqParser {
             other_field,
             epsilon,
             max_relative,
+            max_ulps,
             set_equal: field_with_args.args.set_equal.unwrap_or(false),
             mapping,
             use_iterator: field_with_args.args.use_iterator.unwrap_or(false),
+            use_map_keys: field_with_args.args.use_map_keys.unwrap_or(false),
+            compare_with,
+            skip_condition,
+            fallible_cast,
         })
     }
 
@@ -189,72 +360,13 @@ impl AbsDiffEqParser {
         &self,
         fields_with_args: &[FieldWithArgs],
     ) -> Vec<proc_macro2::TokenStream> {
-        // We need to extend the where clause for all generics
-        let fields = fields_with_args
+        fields_with_args
             .iter()
             .enumerate()
             .filter_map(|(n, field_with_args)| {
-                if let Some(FieldFormatted {
-                    base_type,
-                    own_field,
-                    other_field,
-                    epsilon,
-                    #[allow(unused)]
-                    max_relative,
-                    set_equal,
-                    mapping,
-                    use_iterator,
-                }) = self.format_nth_field(n, field_with_args, None)
-                {
-                    if set_equal {
-                        Some(quote::quote!(#own_field == #other_field &&))
-                    } else if let Some(map) = mapping {
-                        Some(quote::quote!(
-                            (if let ((Some(a), Some(b))) = (
-                                (#map)(#own_field),
-                                (#map)(#other_field)
-                            ) {
-                                #ApproxName::AbsDiffEq::abs_diff_eq(&a, &b, #epsilon)
-                            } else {
-                                false
-                            }) &&
-                        ))
-                    } else if use_iterator {
-                        Some(quote::quote!(({
-                            let mut iter1 = core::iter::IntoIterator::into_iter(#own_field);
-                            let mut iter2 = core::iter::IntoIterator::into_iter(#other_field);
-                            let mut res = true;
-                            loop {
-                                match (iter1.next(), iter2.next()) {
-                                    (None, None) => break,
-                                    (Some(a), Some(b)) => {
-                                        if !#ApproxName::AbsDiffEq::abs_diff_eq(a, b, #epsilon) {
-                                            res = false;
-                                            break;
-                                        }
-                                    },
-                                    _ => {
-                                        res = false;
-                                        break;
-                                    }
-                                }
-                            }
-                            res
-                        }) &&))
-                    } else {
-                        Some(quote::quote!(
-                            <#base_type as #ApproxName::AbsDiffEq>::abs_diff_eq(
-                                #own_field,
-                                #other_field,
-                                #epsilon
-                            ) &&
-                        ))
-                    }
-                } else {
-                    None
-                }
-            });
-        fields.collect()
+                self.get_single_field_check(ApproxTrait::Abs, n, field_with_args, None)
+            })
+            .collect()
     }
 
     pub fn get_abs_diff_eq_enum_variants(
@@ -264,182 +376,32 @@ impl AbsDiffEqParser {
         variants_with_args
             .iter()
             .map(|variant_with_args| {
-                let variant = &variant_with_args.ident;
-                use syn::spanned::Spanned;
-
-                let gen_field_names = |var: &str| -> Vec<syn::Ident> {
-                    variant_with_args
-                        .fields_with_args
-                        .iter()
-                        .enumerate()
-                        .map(|(n, field)| syn::Ident::new(&format!("{var}{n}"), field.ident.span()))
-                        .collect()
-                };
-                if variant_with_args
+                let (pattern1, pattern2, idents) = variant_with_args.get_match_patterns();
+                let comps: Vec<_> = variant_with_args
                     .fields_with_args
-                    .first()
-                    .and_then(|f| f.ident.clone())
-                    .is_some()
-                {
-                    let field_placeholders1 = gen_field_names("x");
-                    let field_placeholders2 = gen_field_names("y");
-                    let gen_combos = |iterator: Vec<syn::Ident>| {
-                        iterator
-                            .iter()
-                            .zip(&variant_with_args.fields_with_args)
-                            .map(|(fph, fwa)| {
-                                let id = &fwa.ident;
-                                quote::quote!(#id: #fph)
-                            })
-                            .collect::<Vec<_>>()
-                    };
-                    let comps: Vec<_> = field_placeholders1
-                        .iter()
-                        .zip(field_placeholders2.iter())
-                        .zip(variant_with_args.fields_with_args.iter())
-                        .map(|((xi, yi), field)| {
-                            self.get_abs_diff_eq_single_field(xi.clone(), yi.clone(), field)
-                        })
-                        .collect();
-                    let field_name_placeholder_combos1 = gen_combos(field_placeholders1);
-                    let field_name_placeholder_combos2 = gen_combos(field_placeholders2);
-                    quote::quote!(
-                        (
-                            Self:: #variant {
-                                #(#field_name_placeholder_combos1),*
-                            },
-                            Self:: #variant {
-                                #(#field_name_placeholder_combos2),*
-                            }
-                        ) => #(#comps) &&*,
-                    )
-                } else if !variant_with_args.fields_with_args.is_empty() {
-                    let field_names1 = gen_field_names("x");
-                    let field_names2 = gen_field_names("y");
-                    let comps: Vec<_> = field_names1
-                        .iter()
-                        .zip(field_names2.iter())
-                        .zip(variant_with_args.fields_with_args.iter())
-                        .map(|((xi, yi), field)| {
-                            self.get_abs_diff_eq_single_field(xi.clone(), yi.clone(), field)
-                        })
-                        .collect();
-                    quote::quote!(
-                        (
-                            Self:: #variant (#(#field_names1),*),
-                            Self:: #variant (#(#field_names2),*)
-                        ) => {#(#comps) &&*},
-                    )
-                } else {
-                    quote::quote!(
-                        (Self:: #variant, Self:: #variant) => true,
-                    )
-                }
+                    .iter()
+                    .zip(idents)
+                    .filter_map(|(field, idents)| {
+                        self.get_single_field_check(ApproxTrait::Abs, 0, field, Some(idents))
+                    })
+                    .collect();
+                quote::quote!(
+                    (#pattern1, #pattern2) => #(#comps &&)* true,
+                )
             })
             .collect()
     }
 
-    pub fn get_abs_diff_eq_single_field(
-        &self,
-        xi: syn::Ident,
-        yi: syn::Ident,
-        field_with_args: &FieldWithArgs,
-    ) -> Option<proc_macro2::TokenStream> {
-        if let Some(FieldFormatted {
-            base_type,
-            own_field,
-            other_field,
-            epsilon,
-            #[allow(unused)]
-            max_relative,
-            set_equal,
-            mapping,
-            use_iterator,
-        }) = self.format_nth_field(0, field_with_args, Some((xi, yi)))
-        {
-            if set_equal {
-                Some(quote::quote!(#own_field == #other_field))
-            } else if let Some(map) = mapping {
-                Some(quote::quote!(
-                    (if let ((Some(a), Some(b))) = (
-                        (#map)(#own_field),
-                        (#map)(#other_field)
-                    ) {
-                        #ApproxName::AbsDiffEq::abs_diff_eq(&a, &b, #epsilon)
-                    } else {
-                        false
-                    })
-                ))
-            } else if use_iterator {
-                Some(quote::quote!({
-                    let mut iter1 = core::iter::IntoIterator::into_iter(*#own_field);
-                    let mut iter2 = core::iter::IntoIterator::into_iter(*#other_field);
-                    let mut res = true;
-                    loop {
-                        match (iter1.next(), iter2.next()) {
-                            (None, None) => break,
-                            (Some(a), Some(b)) => {
-                                if !#ApproxName::AbsDiffEq::abs_diff_eq(a, b, #epsilon) {
-                                    res = false;
-                                    break;
-                                }
-                            },
-                            _ => {
-                                res = false;
-                                break;
-                            }
-                        }
-                    }
-                    res
-                }))
-            } else {
-                Some(quote::quote!(
-                    <#base_type as #ApproxName::AbsDiffEq>::abs_diff_eq(
-                        &#own_field,
-                        &#other_field,
-                        #epsilon
-                    )
-                ))
-            }
-        } else {
-            None
-        }
-    }
-
-    pub fn generate_where_clause(&self, abs_diff_eq: bool) -> proc_macro2::TokenStream {
-        let (epsilon_type, _) = self.get_epsilon_type_and_default_value();
-        let (_, _, where_clause) = self.base_type.generics().split_for_impl();
-        let trait_bound = match abs_diff_eq {
-            true => quote::quote!(#ApproxName::AbsDiffEq),
-            false => quote::quote!(#ApproxName::RelativeEq),
-        };
-        if self.generics_involved() {
-            let parent = self.get_epsilon_parent_type();
-            match where_clause {
-                Some(clause) => quote::quote!(
-                    #clause
-                        #parent: #trait_bound,
-                        #parent: PartialEq,
-                        #epsilon_type: Clone,
-                ),
-                None => quote::quote!(
-                where
-                    #parent: #trait_bound,
-                    #parent: PartialEq,
-                    #epsilon_type: Clone,
-                ),
-            }
-        } else {
-            quote::quote!(#where_clause)
-        }
-    }
-
     pub fn implement_derive_abs_diff_eq(&self) -> proc_macro2::TokenStream {
         let struct_name = &self.base_type.ident();
         let (epsilon_type, epsilon_default_value) = self.get_epsilon_type_and_default_value();
 
         let (impl_generics, ty_generics, _) = self.base_type.generics().split_for_impl();
-        let where_clause = self.generate_where_clause(true);
+        let where_clause = self.generate_where_clause(ApproxTrait::Abs);
+        let rhs_type = self.get_rhs_type();
+        let trait_rhs = self.get_trait_rhs();
+        let special_values = self.get_special_values_helpers_if_used();
+        let missing_tolerances = self.get_missing_tolerances(ApproxTrait::Abs);
 
         match &self.base_type {
             #[allow(unused)]
@@ -448,11 +410,21 @@ impl AbsDiffEqParser {
                 fields_with_args,
             } => {
                 let fields = self.get_abs_diff_eq_struct_fields(fields_with_args);
+                let check = self
+                    .with_computed_properties(ApproxTrait::Abs, quote::quote!(#(#fields &&)* true));
+                let epsilon_struct = match self.uses_epsilon_struct() {
+                    true => self.implement_epsilon_struct(),
+                    false => quote::quote!(),
+                };
 
                 quote::quote!(
+                    #epsilon_struct
+
                     const _ : () = {
+                        #special_values
+
                         #[automatically_derived]
-                        impl #impl_generics #ApproxName::AbsDiffEq for #struct_name #ty_generics
+                        impl #impl_generics #ApproxName::AbsDiffEq #trait_rhs for #struct_name #ty_generics
                         #where_clause
                         {
                             type Epsilon = #epsilon_type;
@@ -461,9 +433,13 @@ impl AbsDiffEqParser {
                                 #epsilon_default_value
                             }
 
-                            fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
-                                #(#fields)*
-                                true
+                            fn abs_diff_eq(
+                                &self,
+                                other: &#rhs_type,
+                                epsilon: Self::Epsilon
+                            ) -> bool {
+                                #missing_tolerances
+                                #check
                             }
                         }
                     };
@@ -475,10 +451,19 @@ impl AbsDiffEqParser {
                 variants_with_args,
             } => {
                 let variants = self.get_abs_diff_eq_enum_variants(variants_with_args);
+                let check = self.with_computed_properties(
+                    ApproxTrait::Abs,
+                    quote::quote!(match (self, other) {
+                        #(#variants)*
+                        _ => false,
+                    }),
+                );
                 quote::quote!(
                     const _: () = {
+                        #special_values
+
                         #[automatically_derived]
-                        impl #impl_generics #ApproxName::AbsDiffEq for #struct_name #ty_generics
+                        impl #impl_generics #ApproxName::AbsDiffEq #trait_rhs for #struct_name #ty_generics
                         #where_clause
                         {
                             type Epsilon = #epsilon_type;
@@ -488,10 +473,8 @@ impl AbsDiffEqParser {
                             }
 
                             fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
-                                match (self, other) {
-                                    #(#variants)*
-                                    _ => false,
-                                }
+                                #missing_tolerances
+                                #check
                             }
                         }
                     };
@@ -0,0 +1,81 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::compare::*;
+use crate::AbsDiffEqParser;
+
+/// Evaluates the closure of a computed property for `self` and `other` and binds the results to
+/// `a` and `b`.
+///
+/// The closure is passed to a generic function such that the types of its arguments are inferred
+/// even if they are not annotated.
+pub fn get_computed_values(property: &ComputedProperty) -> proc_macro2::TokenStream {
+    let closure = &property.closure;
+    quote::quote!(
+        fn __approx_evaluate<T: ?Sized, R>(f: impl Fn(&T) -> R, a: &T, b: &T) -> (R, R) {
+            (f(a), f(b))
+        }
+        let (a, b) = __approx_evaluate(#closure, self, other);
+    )
+}
+
+/// The tolerances of a computed property which fall back to the tolerances of the method
+pub fn get_computed_tolerances(
+    property: &ComputedProperty,
+) -> (
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+    proc_macro2::TokenStream,
+) {
+    let tolerance = |value: &Option<syn::Expr>, default| match value {
+        Some(value) => quote::quote!((#value)),
+        None => default,
+    };
+    (
+        tolerance(&property.epsilon, quote::quote!(epsilon.clone())),
+        tolerance(&property.max_relative, quote::quote!(max_relative.clone())),
+        tolerance(&property.max_ulps, quote::quote!(max_ulps)),
+    )
+}
+
+impl AbsDiffEqParser {
+    /// Ensures that computed properties can be evaluated for both compared objects.
+    pub fn check_computed_properties(&self) -> syn::Result<()> {
+        if let (Some(property), Some(_)) = (self.struct_args.also.first(), &self.struct_args.rhs) {
+            return Err(syn::Error::new(
+                property.name.span(),
+                "computed properties can not be combined with rhs",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Extends the comparison of all fields by the computed properties given by
+    /// `#[approx(also(...))]`.
+    pub fn with_computed_properties(
+        &self,
+        approx_trait: ApproxTrait,
+        check: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        if self.struct_args.also.is_empty() {
+            return check;
+        }
+        let properties = self.struct_args.also.iter().map(|property| {
+            let values = get_computed_values(property);
+            let (epsilon, max_relative, max_ulps) = get_computed_tolerances(property);
+            let comparison = get_trait_comparison(
+                approx_trait,
+                None,
+                quote::quote!(&a),
+                quote::quote!(&b),
+                &epsilon,
+                &max_relative,
+                &max_ulps,
+            );
+            quote::quote!({
+                #values
+                #comparison
+            })
+        });
+        quote::quote!((#check) #(&& #properties)*)
+    }
+}
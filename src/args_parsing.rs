@@ -1,10 +1,302 @@
+use crate::base_types::ApproxTrait;
+
+/// Casts the field to the type of the epsilon value (`cast_field`) or the tolerances to the type
+/// of the field (`cast_value`)
 #[derive(Clone)]
 pub enum TypeCast {
-    CastField,
-    CastValue,
+    CastField(CastOptions),
+    CastValue(CastOptions),
+}
+
+impl TypeCast {
+    pub fn options(&self) -> &CastOptions {
+        match self {
+            Self::CastField(options) | Self::CastValue(options) => options,
+        }
+    }
+}
+
+/// Conversions which can be used by `#[approx(cast_field = ...)]` and
+/// `#[approx(cast_value = ...)]`
+#[derive(Clone, Copy, Default, PartialEq)]
+pub enum CastMethod {
+    /// Primitive casts by `value as Type` (default)
+    #[default]
+    As,
+    /// Conversions by `Into<Type>`
+    Into,
+    /// Conversions by `TryInto<Type>` which fail the comparison on error
+    TryInto,
+}
+
+/// Options of `#[approx(cast_field(...))]` and `#[approx(cast_value(...))]`
+#[derive(Clone, Default)]
+pub struct CastOptions {
+    pub method: CastMethod,
+    /// Explicit target type which replaces the inferred one
+    pub to: Option<syn::Type>,
+}
+
+const CAST_OPTIONS: &[&str] = &["to", "by"];
+
+impl CastMethod {
+    fn parse_method(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        use syn::ext::IdentExt;
+        let method = input.call(syn::Ident::parse_any)?;
+        match method.to_string().as_str() {
+            "as" => Ok(Self::As),
+            "into" => Ok(Self::Into),
+            "try_into" => Ok(Self::TryInto),
+            _ => Err(syn::Error::new(
+                method.span(),
+                format!("unknown cast method `{method}`, expected one of `as`, `into`, `try_into`"),
+            )),
+        }
+    }
+}
+
+impl syn::parse::Parse for CastOptions {
+    /// Parses the optional remainder of `cast_field = method` or `cast_field(to = Type, by = method)`
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+        if input.peek(syn::Token![=]) {
+            let _: syn::Token![=] = input.parse()?;
+            options.method = CastMethod::parse_method(input)?;
+            return Ok(options);
+        }
+        if !input.peek(syn::token::Paren) {
+            return Ok(options);
+        }
+        let content;
+        syn::parenthesized!(content in input);
+        let mut seen = SeenArgs::default();
+        while !content.is_empty() {
+            let keyword: syn::Ident = content.parse()?;
+            if !CAST_OPTIONS.contains(&keyword.to_string().as_str()) {
+                return Err(unknown_arg_error(&keyword, &[], CAST_OPTIONS));
+            }
+            seen.insert(&keyword)?;
+            let _: syn::Token![=] = content.parse()?;
+            match keyword.to_string().as_str() {
+                "to" => options.to = Some(content.parse()?),
+                _ => options.method = CastMethod::parse_method(&content)?,
+            }
+            if content.is_empty() {
+                break;
+            }
+            let _: syn::Token![,] = content.parse()?;
+        }
+        Ok(options)
+    }
+}
+
+/// Norms which can be used to compare iterators by `#[approx(into_iter, norm = ...)]`
+#[derive(Clone, Copy)]
+pub enum Norm {
+    L1,
+    L2,
+    LInf,
+}
+
+/// Options of `#[approx(into_iter(...))]` which determine the compared elements
+#[derive(Clone, Default)]
+pub struct IterOptions {
+    /// Either `strict` (default) or `shortest`
+    pub length: Option<syn::Ident>,
+    pub skip: Option<syn::Expr>,
+    pub take: Option<syn::Expr>,
+    pub stride: Option<syn::Expr>,
+}
+
+const ITER_OPTIONS: &[&str] = &["length", "skip", "take", "stride"];
+
+const LENGTH_POLICIES: &[&str] = &["strict", "shortest"];
+
+impl IterOptions {
+    /// Only compare elements until one of the iterators is exhausted
+    pub fn is_shortest(&self) -> bool {
+        self.length.as_ref().is_some_and(|x| x == "shortest")
+    }
+}
+
+impl syn::parse::Parse for IterOptions {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut options = Self::default();
+        let mut seen = SeenArgs::default();
+        while !input.is_empty() {
+            let keyword: syn::Ident = input.parse()?;
+            if !ITER_OPTIONS.contains(&keyword.to_string().as_str()) {
+                return Err(unknown_arg_error(&keyword, &[], ITER_OPTIONS));
+            }
+            seen.insert(&keyword)?;
+            let _: syn::Token![=] = input.parse()?;
+            match keyword.to_string().as_str() {
+                "length" => {
+                    let policy: syn::Ident = input.parse()?;
+                    if !LENGTH_POLICIES.contains(&policy.to_string().as_str()) {
+                        return Err(syn::Error::new(
+                            policy.span(),
+                            format!(
+                                "unknown length policy `{policy}`, expected `strict` or `shortest`"
+                            ),
+                        ));
+                    }
+                    options.length = Some(policy);
+                }
+                "skip" => options.skip = Some(input.parse()?),
+                "take" => options.take = Some(input.parse()?),
+                _ => options.stride = Some(input.parse()?),
+            }
+            if input.is_empty() {
+                break;
+            }
+            let _: syn::Token![,] = input.parse()?;
+        }
+        Ok(options)
+    }
+}
+
+/// Tolerances of a named profile specified by `#[approx(profile(name(...)))]`
+#[derive(Clone)]
+pub struct Profile {
+    pub name: syn::Ident,
+    pub epsilon: Option<syn::Expr>,
+    pub max_relative: Option<syn::Expr>,
+    pub max_ulps: Option<syn::Expr>,
+}
+
+/// Tolerances of a profile on the object in the order `epsilon`, `max_relative`, `max_ulps`
+const STRUCT_PROFILE_OPTIONS: &[&str; 3] = &[
+    "default_epsilon",
+    "default_max_relative",
+    "default_max_ulps",
+];
+
+/// Tolerances of a profile on a field in the order `epsilon`, `max_relative`, `max_ulps`
+const FIELD_PROFILE_OPTIONS: &[&str; 3] =
+    &["static_epsilon", "static_max_relative", "static_max_ulps"];
+
+impl Profile {
+    /// Ensures that no profile of the same name was specified before.
+    fn check_unique(&self, profiles: &[Profile]) -> syn::Result<()> {
+        if profiles.iter().any(|x| x.name == self.name) {
+            return Err(syn::Error::new(
+                self.name.span(),
+                format!("the profile `{}` is specified more than once", self.name),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Parses `(name(option = value, ...))` where the options are named by `options`.
+    ///
+    /// Nesting the options inside the name keeps the options of different profiles apart such
+    /// that clippy does not consider them as duplicated attributes.
+    fn parse_with_options(
+        input: syn::parse::ParseStream,
+        options: &[&str; 3],
+    ) -> syn::Result<Self> {
+        let outer;
+        syn::parenthesized!(outer in input);
+        let mut profile = Self {
+            name: outer.parse()?,
+            epsilon: None,
+            max_relative: None,
+            max_ulps: None,
+        };
+        if !outer.peek(syn::token::Paren) {
+            return Err(syn::Error::new(
+                profile.name.span(),
+                format!(
+                    "expected the tolerances of the profile: `profile({}(...))`",
+                    profile.name
+                ),
+            ));
+        }
+        let content;
+        syn::parenthesized!(content in outer);
+        if !outer.is_empty() {
+            return Err(outer.error("expected a single profile: `profile(name(...))`"));
+        }
+        let mut seen = SeenArgs::default();
+        while !content.is_empty() {
+            let keyword: syn::Ident = content.parse()?;
+            let position = options.iter().position(|option| keyword == option);
+            if position.is_none() {
+                return Err(unknown_arg_error(&keyword, &[], options));
+            }
+            seen.insert(&keyword)?;
+            let _: syn::Token![=] = content.parse()?;
+            let value = Some(content.parse()?);
+            match position {
+                Some(0) => profile.epsilon = value,
+                Some(1) => profile.max_relative = value,
+                _ => profile.max_ulps = value,
+            }
+            if content.is_empty() {
+                break;
+            }
+            let _: syn::Token![,] = content.parse()?;
+        }
+        Ok(profile)
+    }
+}
+
+/// Comparison of a computed property specified by `#[approx(also(name = closure, ...))]`
+#[derive(Clone)]
+pub struct ComputedProperty {
+    pub name: syn::Ident,
+    pub closure: syn::Expr,
+    pub epsilon: Option<syn::Expr>,
+    pub max_relative: Option<syn::Expr>,
+    pub max_ulps: Option<syn::Expr>,
+}
+
+/// Tolerances which can be specified for a computed property
+const COMPUTED_PROPERTY_OPTIONS: &[&str] = &["epsilon", "max_relative", "max_ulps"];
+
+impl syn::parse::Parse for ComputedProperty {
+    /// Parses `(name = closure, option = value, ...)`
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+        let name = content.parse()?;
+        let _: syn::Token![=] = content.parse()?;
+        let mut property = Self {
+            name,
+            closure: content.parse()?,
+            epsilon: None,
+            max_relative: None,
+            max_ulps: None,
+        };
+        let mut seen = SeenArgs::default();
+        while !content.is_empty() {
+            let _: syn::Token![,] = content.parse()?;
+            if content.is_empty() {
+                break;
+            }
+            let keyword: syn::Ident = content.parse()?;
+            if !COMPUTED_PROPERTY_OPTIONS
+                .iter()
+                .any(|option| keyword == option)
+            {
+                return Err(unknown_arg_error(&keyword, &[], COMPUTED_PROPERTY_OPTIONS));
+            }
+            seen.insert(&keyword)?;
+            let _: syn::Token![=] = content.parse()?;
+            let value = Some(content.parse()?);
+            match keyword.to_string().as_str() {
+                "epsilon" => property.epsilon = value,
+                "max_relative" => property.max_relative = value,
+                _ => property.max_ulps = value,
+            }
+        }
+        Ok(property)
+    }
 }
 
 /// Represents a field in a struct definition
+#[derive(Clone)]
 pub struct FieldWithArgs {
     pub ident: Option<syn::Ident>,
     pub ty: syn::Type,
@@ -24,25 +316,93 @@ impl FieldWithArgs {
         let args = FieldArgs::from_attrs(&field.attrs)?;
         Ok(Self { ident, ty, args })
     }
+
+    /// Ensures that the tolerance expressions of the `n`th field of a struct do not access the
+    /// field itself.
+    pub fn check_tolerance_references(&self, n: usize) -> syn::Result<()> {
+        let member = match &self.ident {
+            Some(ident) => ident.to_string(),
+            None => n.to_string(),
+        };
+        let expressions = [&self.args.epsilon_value, &self.args.max_relative_value];
+        for expr in expressions.into_iter().flatten() {
+            if let Some(span) = find_member_access(quote::quote!(#expr), &member) {
+                return Err(syn::Error::new(
+                    span,
+                    format!("the tolerance of `{member}` can not depend on the field itself"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Finds an access to the given member of `self` or `other` such as `self.sigma` or `other.0`.
+fn find_member_access(tokens: proc_macro2::TokenStream, member: &str) -> Option<proc_macro2::Span> {
+    let tokens: Vec<_> = tokens.into_iter().collect();
+    tokens
+        .iter()
+        .enumerate()
+        .find_map(|(i, token)| match token {
+            proc_macro2::TokenTree::Group(group) => find_member_access(group.stream(), member),
+            proc_macro2::TokenTree::Ident(ident) if ident == "self" || ident == "other" => {
+                match (tokens.get(i + 1), tokens.get(i + 2)) {
+                    (Some(proc_macro2::TokenTree::Punct(punct)), Some(accessed))
+                        if punct.as_char() == '.' && accessed.to_string() == member =>
+                    {
+                        Some(accessed.span())
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        })
 }
 
 /// All arguments that can be specified and parsed in a field
+#[derive(Clone)]
 pub struct FieldArgs {
     pub skip: Option<bool>,
+    pub skip_if: Option<syn::Expr>,
+    pub compare_if: Option<syn::Expr>,
     pub set_equal: Option<bool>,
     pub cast_strategy: Option<TypeCast>,
     pub epsilon_static_value: Option<syn::Expr>,
     pub max_relative_static_value: Option<syn::Expr>,
+    pub max_ulps_static_value: Option<syn::Expr>,
+    pub epsilon_value: Option<syn::Expr>,
+    pub max_relative_value: Option<syn::Expr>,
     pub mapping: Option<syn::Expr>,
     pub epsilon_mapping: Option<syn::Expr>,
     pub max_relative_mapping: Option<syn::Expr>,
+    pub max_ulps_mapping: Option<syn::Expr>,
     pub use_iterator: Option<bool>,
+    pub iter_options: Option<IterOptions>,
+    pub unordered: Option<bool>,
+    pub norm: Option<Norm>,
+    pub compare: Option<ApproxTrait>,
+    pub periodic: Option<syn::Expr>,
+    pub nan_equal: Option<bool>,
+    pub inf_equal: Option<bool>,
+    pub use_map_keys: Option<bool>,
+    pub with_module: Option<syn::Path>,
+    pub compare_with: Option<syn::Expr>,
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    pub groups: Vec<syn::Ident>,
+    pub profiles: Vec<Profile>,
+    /// All specified arguments which are checked for conflicts
+    seen: SeenArgs,
 }
 
 impl FieldArgs {
-    pub fn patch_if_not_exists(&mut self, other: &Self) {
+    /// Fills in all arguments which are not specified by the arguments of `other`.
+    ///
+    /// Since the combination can contain conflicting arguments, it is validated again.
+    pub fn patch_if_not_exists(&mut self, other: &Self) -> syn::Result<()> {
         *self = Self {
             skip: self.skip.or(other.skip),
+            skip_if: self.skip_if.clone().or(other.skip_if.clone()),
+            compare_if: self.compare_if.clone().or(other.compare_if.clone()),
             set_equal: self.set_equal.or(other.set_equal),
             cast_strategy: self.cast_strategy.clone().or(other.cast_strategy.clone()),
             epsilon_static_value: self
@@ -53,6 +413,15 @@ impl FieldArgs {
                 .max_relative_static_value
                 .clone()
                 .or(other.max_relative_static_value.clone()),
+            max_ulps_static_value: self
+                .max_ulps_static_value
+                .clone()
+                .or(other.max_ulps_static_value.clone()),
+            epsilon_value: self.epsilon_value.clone().or(other.epsilon_value.clone()),
+            max_relative_value: self
+                .max_relative_value
+                .clone()
+                .or(other.max_relative_value.clone()),
             mapping: self.mapping.clone().or(other.mapping.clone()),
             epsilon_mapping: self
                 .epsilon_mapping
@@ -62,50 +431,465 @@ impl FieldArgs {
                 .max_relative_mapping
                 .clone()
                 .or(other.max_relative_mapping.clone()),
+            max_ulps_mapping: self
+                .max_ulps_mapping
+                .clone()
+                .or(other.max_ulps_mapping.clone()),
             use_iterator: self.use_iterator.or(other.use_iterator),
+            iter_options: self.iter_options.clone().or(other.iter_options.clone()),
+            unordered: self.unordered.or(other.unordered),
+            norm: self.norm.or(other.norm),
+            compare: self.compare.or(other.compare),
+            periodic: self.periodic.clone().or(other.periodic.clone()),
+            nan_equal: self.nan_equal.or(other.nan_equal),
+            inf_equal: self.inf_equal.or(other.inf_equal),
+            use_map_keys: self.use_map_keys.or(other.use_map_keys),
+            with_module: self.with_module.clone().or(other.with_module.clone()),
+            compare_with: self.compare_with.clone().or(other.compare_with.clone()),
+            bound: self.bound.clone().or(other.bound.clone()),
+            groups: self
+                .groups
+                .iter()
+                .chain(other.groups.iter())
+                .cloned()
+                .collect(),
+            profiles: self
+                .profiles
+                .iter()
+                .chain(other.profiles.iter())
+                .cloned()
+                .collect(),
+            seen: self.seen.merge(&other.seen),
         };
+        self.check_combinations()
+    }
+
+    /// Ensures that the specified arguments do not conflict with each other and that all
+    /// required arguments are present.
+    fn check_combinations(&self) -> syn::Result<()> {
+        self.seen.check_conflicts(FIELD_CONFLICTS)?;
+        self.seen.check_requirements(FIELD_REQUIREMENTS)?;
+        if let (Some(true), Some(length)) = (
+            self.unordered,
+            self.iter_options.as_ref().and_then(|x| x.length.as_ref()),
+        ) {
+            if length == "shortest" {
+                return Err(syn::Error::new(
+                    length.span(),
+                    "`length = shortest` can not be combined with `unordered`",
+                ));
+            }
+        }
+        Ok(())
     }
 }
 
 /// Every value argument specified by `#[approx(value)]`
 pub enum FieldValueArg {
     Skip,
-    CastStrategy(TypeCast),
+    CastStrategy(Box<TypeCast>),
     Equal,
-    Iter,
+    Iter(Option<Box<IterOptions>>),
+    Unordered,
+    MapKeys,
+    NanEqual,
+    InfEqual,
+    Profile(Box<Profile>),
+}
+
+const FIELD_VALUE_ARGS: &[&str] = &[
+    "skip",
+    "cast_field",
+    "cast_value",
+    "equal",
+    "into_iter",
+    "unordered",
+    "map_keys",
+    "nan_equal",
+    "inf_equal",
+    "profile",
+];
+
+const FIELD_KEY_VALUE_ARGS: &[&str] = &[
+    "skip_if",
+    "compare_if",
+    "static_epsilon",
+    "static_max_relative",
+    "static_max_ulps",
+    "epsilon",
+    "max_relative",
+    "map",
+    "epsilon_map",
+    "max_relative_map",
+    "max_ulps_map",
+    "with",
+    "compare_with",
+    "norm",
+    "compare",
+    "periodic",
+    "bound",
+    "group",
+];
+
+/// Pairs of field arguments which can not be used together
+const FIELD_CONFLICTS: &[(&str, &str)] = &[
+    ("skip", "equal"),
+    ("skip", "map"),
+    ("skip", "cast_field"),
+    ("skip", "cast_value"),
+    ("skip", "into_iter"),
+    ("skip", "map_keys"),
+    ("skip", "with"),
+    ("skip", "compare_with"),
+    ("cast_field", "cast_value"),
+    ("equal", "map"),
+    ("equal", "cast_field"),
+    ("equal", "cast_value"),
+    ("equal", "into_iter"),
+    ("equal", "map_keys"),
+    ("map", "into_iter"),
+    ("map", "map_keys"),
+    ("with", "compare_with"),
+    ("equal", "with"),
+    ("equal", "compare_with"),
+    ("map", "with"),
+    ("map", "compare_with"),
+    ("into_iter", "with"),
+    ("into_iter", "compare_with"),
+    ("into_iter", "map_keys"),
+    ("map_keys", "with"),
+    ("map_keys", "compare_with"),
+    ("unordered", "norm"),
+    ("skip", "periodic"),
+    ("equal", "periodic"),
+    ("map", "periodic"),
+    ("with", "periodic"),
+    ("compare_with", "periodic"),
+    ("norm", "periodic"),
+    ("skip", "compare"),
+    ("equal", "compare"),
+    ("with", "compare"),
+    ("compare_with", "compare"),
+    ("epsilon", "static_epsilon"),
+    ("epsilon", "epsilon_map"),
+    ("max_relative", "static_max_relative"),
+    ("max_relative", "max_relative_map"),
+    ("skip", "skip_if"),
+    ("skip", "compare_if"),
+    ("skip_if", "compare_if"),
+    ("skip", "group"),
+    ("skip", "profile"),
+];
+
+/// Pairs of field arguments where the first one can only be used together with the second one
+const FIELD_REQUIREMENTS: &[(&str, &str)] = &[("unordered", "into_iter"), ("norm", "into_iter")];
+
+const STRUCT_VALUE_ARGS: &[&str] = &[
+    "epsilon_struct",
+    "nan_equal",
+    "inf_equal",
+    "profile",
+    "also",
+    "transparent",
+    "mismatches",
+];
+
+const STRUCT_KEY_VALUE_ARGS: &[&str] = &[
+    "epsilon_type",
+    "default_epsilon",
+    "default_max_relative",
+    "default_max_ulps",
+    "rhs",
+    "bound",
+    "remote",
+    "via",
+    "convert",
+];
+
+/// Pairs of object arguments which can not be used together
+const STRUCT_CONFLICTS: &[(&str, &str)] = &[
+    ("profile", "epsilon_struct"),
+    ("epsilon_type", "epsilon_struct"),
+    ("remote", "rhs"),
+    ("remote", "epsilon_struct"),
+    ("remote", "profile"),
+    ("transparent", "epsilon_type"),
+    ("transparent", "default_epsilon"),
+    ("transparent", "default_max_relative"),
+    ("transparent", "default_max_ulps"),
+    ("transparent", "rhs"),
+    ("transparent", "epsilon_struct"),
+    ("transparent", "nan_equal"),
+    ("transparent", "inf_equal"),
+    ("transparent", "profile"),
+    ("transparent", "also"),
+    ("transparent", "remote"),
+    ("via", "transparent"),
+    ("via", "epsilon_type"),
+    ("via", "default_epsilon"),
+    ("via", "default_max_relative"),
+    ("via", "default_max_ulps"),
+    ("via", "rhs"),
+    ("via", "epsilon_struct"),
+    ("via", "nan_equal"),
+    ("via", "inf_equal"),
+    ("via", "profile"),
+    ("via", "also"),
+    ("via", "remote"),
+    ("remote", "mismatches"),
+    ("transparent", "mismatches"),
+    ("via", "mismatches"),
+];
+
+/// Pairs of object arguments where the first one can only be used together with the second one
+const STRUCT_REQUIREMENTS: &[(&str, &str)] = &[("convert", "via")];
+
+/// Number of single character edits needed to turn one word into the other
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Creates an error for an unknown argument which suggests the closest known argument.
+fn unknown_arg_error(
+    ident: &syn::Ident,
+    value_args: &[&str],
+    key_value_args: &[&str],
+) -> syn::Error {
+    let name = ident.to_string();
+    let suggestion = value_args
+        .iter()
+        .chain(key_value_args.iter())
+        .map(|candidate| (edit_distance(&name, candidate), candidate))
+        .filter(|(distance, candidate)| *distance <= 2.max(candidate.len() / 3))
+        .min_by_key(|(distance, _)| *distance);
+    match suggestion {
+        Some((_, candidate)) => syn::Error::new(
+            ident.span(),
+            format!("unknown approx attribute `{name}`, did you mean `{candidate}`?"),
+        ),
+        None => {
+            let expected = value_args
+                .iter()
+                .chain(key_value_args.iter())
+                .map(|x| format!("`{x}`"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            syn::Error::new(
+                ident.span(),
+                format!("unknown approx attribute `{name}`, expected one of {expected}"),
+            )
+        }
+    }
+}
+
+/// Checks that an argument is used in the correct form `#[approx(value)]` or
+/// `#[approx(key = value)]`.
+fn check_arg_form(
+    ident: &syn::Ident,
+    has_value: bool,
+    value_args: &[&str],
+    key_value_args: &[&str],
+) -> syn::Result<()> {
+    let name = ident.to_string();
+    match (
+        has_value,
+        value_args.contains(&name.as_str()),
+        key_value_args.contains(&name.as_str()),
+    ) {
+        (true, _, true) | (false, true, _) => Ok(()),
+        (true, true, _) => Err(syn::Error::new(
+            ident.span(),
+            format!("`{name}` does not take a value"),
+        )),
+        (false, _, true) => Err(syn::Error::new(
+            ident.span(),
+            format!("`{name}` requires a value: `{name} = ...`"),
+        )),
+        _ => Err(unknown_arg_error(ident, value_args, key_value_args)),
+    }
+}
+
+/// Keeps track of all arguments given to a single item to detect duplicates and conflicts
+#[derive(Clone, Default)]
+struct SeenArgs(Vec<syn::Ident>);
+
+/// Arguments which may be specified more than once
+const REPEATABLE_ARGS: &[&str] = &["group", "profile", "also"];
+
+impl SeenArgs {
+    fn insert(&mut self, ident: &syn::Ident) -> syn::Result<()> {
+        if self.0.iter().any(|seen| seen == ident) {
+            if REPEATABLE_ARGS.iter().any(|arg| ident == arg) {
+                return Ok(());
+            }
+            return Err(syn::Error::new(
+                ident.span(),
+                format!("`{ident}` is specified more than once"),
+            ));
+        }
+        self.0.push(ident.clone());
+        Ok(())
+    }
+
+    /// Combines the arguments of both items where the arguments of `self` take precedence.
+    fn merge(&self, other: &Self) -> Self {
+        let mut merged = self.clone();
+        for ident in other.0.iter() {
+            if !merged.0.iter().any(|seen| seen == ident) {
+                merged.0.push(ident.clone());
+            }
+        }
+        merged
+    }
+
+    fn check_conflicts(&self, conflicts: &[(&str, &str)]) -> syn::Result<()> {
+        for (first, second) in conflicts {
+            let first_seen = self.0.iter().any(|seen| seen == first);
+            if let (true, Some(ident)) = (first_seen, self.0.iter().find(|seen| *seen == second)) {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("`{first}` can not be combined with `{second}`"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_requirements(&self, requirements: &[(&str, &str)]) -> syn::Result<()> {
+        for (dependent, required) in requirements {
+            let required_seen = self.0.iter().any(|seen| seen == required);
+            if let (false, Some(ident)) =
+                (required_seen, self.0.iter().find(|seen| *seen == dependent))
+            {
+                return Err(syn::Error::new(
+                    ident.span(),
+                    format!("`{dependent}` can only be used together with `{required}`"),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 impl FieldValueArg {
     fn from_ident(ident: &syn::Ident) -> syn::Result<Self> {
         match ident.to_string().as_str() {
             "skip" => Ok(FieldValueArg::Skip),
-            "cast_field" => Ok(FieldValueArg::CastStrategy(TypeCast::CastField)),
-            "cast_value" => Ok(FieldValueArg::CastStrategy(TypeCast::CastValue)),
             "equal" => Ok(FieldValueArg::Equal),
-            "into_iter" => Ok(FieldValueArg::Iter),
-            _ => Err(syn::Error::new(ident.span(), "Not a valid value.")),
+            "into_iter" => Ok(FieldValueArg::Iter(None)),
+            "unordered" => Ok(FieldValueArg::Unordered),
+            "map_keys" => Ok(FieldValueArg::MapKeys),
+            "nan_equal" => Ok(FieldValueArg::NanEqual),
+            "inf_equal" => Ok(FieldValueArg::InfEqual),
+            _ => Err(unknown_arg_error(
+                ident,
+                FIELD_VALUE_ARGS,
+                FIELD_KEY_VALUE_ARGS,
+            )),
         }
     }
 }
 
 /// Every key-value pair specified by `#[approx(key = value)]`
 pub enum FieldKeyValueArg {
+    SkipIf(syn::Expr),
+    CompareIf(syn::Expr),
     EpsilonStatic(Option<syn::Expr>),
     MaxRelativeStatic(Option<syn::Expr>),
+    MaxUlpsStatic(Option<syn::Expr>),
+    Epsilon(syn::Expr),
+    MaxRelative(syn::Expr),
     Mapping(Option<syn::Expr>),
     EpsilonMapping(Option<syn::Expr>),
     MaxRelativeMapping(Option<syn::Expr>),
+    MaxUlpsMapping(Option<syn::Expr>),
+    With(syn::Path),
+    CompareWith(syn::Expr),
+    Norm(Norm),
+    Compare(ApproxTrait),
+    Periodic(syn::Expr),
+    Bound(Vec<syn::WherePredicate>),
+    Group(syn::Ident),
+}
+
+/// Parses a string literal of where predicates such as `"T: Clone, U: Default"`
+fn parse_bound(input: syn::parse::ParseStream) -> syn::Result<Vec<syn::WherePredicate>> {
+    let bound: syn::LitStr = input.parse()?;
+    let predicates = bound.parse_with(
+        syn::punctuated::Punctuated::<syn::WherePredicate, syn::Token![,]>::parse_terminated,
+    )?;
+    Ok(predicates.into_iter().collect())
 }
 
 impl FieldKeyValueArg {
     fn parse_value(keyword: &syn::Ident, input: syn::parse::ParseStream) -> syn::Result<Self> {
         match keyword.to_string().as_str() {
+            "skip_if" => Ok(Self::SkipIf(input.parse()?)),
+            "compare_if" => Ok(Self::CompareIf(input.parse()?)),
             "static_epsilon" => Ok(Self::EpsilonStatic(Some(input.parse()?))),
             "static_max_relative" => Ok(Self::MaxRelativeStatic(Some(input.parse()?))),
+            "static_max_ulps" => Ok(Self::MaxUlpsStatic(Some(input.parse()?))),
+            "epsilon" => Ok(Self::Epsilon(input.parse()?)),
+            "max_relative" => Ok(Self::MaxRelative(input.parse()?)),
             "map" => Ok(Self::Mapping(Some(input.parse()?))),
             "epsilon_map" => Ok(Self::EpsilonMapping(Some(input.parse()?))),
             "max_relative_map" => Ok(Self::MaxRelativeMapping(Some(input.parse()?))),
-            _ => Err(syn::Error::new(keyword.span(), "Not a valid keyword")),
+            "max_ulps_map" => Ok(Self::MaxUlpsMapping(Some(input.parse()?))),
+            "with" => Ok(Self::With(input.parse()?)),
+            "compare_with" => Ok(Self::CompareWith(input.parse()?)),
+            "periodic" => Ok(Self::Periodic(input.parse()?)),
+            "norm" => {
+                let norm: syn::Ident = input.parse()?;
+                match norm.to_string().as_str() {
+                    "l1" => Ok(Self::Norm(Norm::L1)),
+                    "l2" => Ok(Self::Norm(Norm::L2)),
+                    "linf" => Ok(Self::Norm(Norm::LInf)),
+                    _ => Err(syn::Error::new(
+                        norm.span(),
+                        format!("unknown norm `{norm}`, expected one of `l1`, `l2`, `linf`"),
+                    )),
+                }
+            }
+            "compare" => {
+                let method: syn::Ident = input.parse()?;
+                match method.to_string().as_str() {
+                    "abs" => Ok(Self::Compare(ApproxTrait::Abs)),
+                    "relative" => Ok(Self::Compare(ApproxTrait::Relative)),
+                    "ulps" => Ok(Self::Compare(ApproxTrait::Ulps)),
+                    _ => Err(syn::Error::new(
+                        method.span(),
+                        format!(
+                            "unknown comparison `{method}`, expected one of `abs`, `relative`, `ulps`"
+                        ),
+                    )),
+                }
+            }
+            "bound" => Ok(Self::Bound(parse_bound(input)?)),
+            "group" => {
+                let name: syn::LitStr = input.parse()?;
+                match name.parse() {
+                    Ok(group) => Ok(Self::Group(group)),
+                    Err(_) => Err(syn::Error::new(
+                        name.span(),
+                        format!("the group `{}` is not a valid identifier", name.value()),
+                    )),
+                }
+            }
+            _ => Err(unknown_arg_error(
+                keyword,
+                FIELD_VALUE_ARGS,
+                FIELD_KEY_VALUE_ARGS,
+            )),
         }
     }
 }
@@ -123,75 +907,194 @@ pub struct StructArgs {
     pub epsilon_type: Option<syn::Type>,
     pub default_epsilon_value: Option<syn::Expr>,
     pub default_max_relative_value: Option<syn::Expr>,
+    pub default_max_ulps_value: Option<syn::Expr>,
+    pub rhs: Option<syn::Type>,
+    pub epsilon_struct: Option<bool>,
+    pub nan_equal: Option<bool>,
+    pub inf_equal: Option<bool>,
+    pub bound: Option<Vec<syn::WherePredicate>>,
+    pub profiles: Vec<Profile>,
+    pub also: Vec<ComputedProperty>,
+    pub remote: Option<syn::Path>,
+    pub transparent: Option<bool>,
+    pub via: Option<syn::Type>,
+    pub convert: Option<syn::Expr>,
+    /// Generates the mismatch report, optionally with a custom name for the type of its entries
+    pub mismatches: Option<Option<syn::Ident>>,
 }
 
 /// Generic Field argument which can be either value or key-value
 pub enum FieldArgGeneric {
-    Value(FieldValueArg),
-    KeyValue(FieldKeyValueArg),
+    Value(syn::Ident, FieldValueArg),
+    KeyValue(syn::Ident, FieldKeyValueArg),
 }
 
 impl syn::parse::Parse for FieldArgGeneric {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let ident: syn::Ident = input.parse()?;
-        if input.peek(syn::Token![=]) {
+        // Casts take an optional method by `cast_field = into` or options by `cast_field(...)`
+        if ident == "cast_field" || ident == "cast_value" {
+            let options: CastOptions = input.parse()?;
+            let cast = match ident == "cast_field" {
+                true => TypeCast::CastField(options),
+                false => TypeCast::CastValue(options),
+            };
+            return Ok(Self::Value(
+                ident,
+                FieldValueArg::CastStrategy(Box::new(cast)),
+            ));
+        }
+        let has_value = input.peek(syn::Token![=]);
+        check_arg_form(&ident, has_value, FIELD_VALUE_ARGS, FIELD_KEY_VALUE_ARGS)?;
+        if has_value {
             let keyword = ident;
             let _: syn::Token![=] = input.parse()?;
             let approx_key_value_arg = FieldKeyValueArg::parse_value(&keyword, input)?;
-            return Ok(Self::KeyValue(approx_key_value_arg));
+            return Ok(Self::KeyValue(keyword, approx_key_value_arg));
+        }
+        if ident == "profile" {
+            let profile = Profile::parse_with_options(input, FIELD_PROFILE_OPTIONS)?;
+            return Ok(Self::Value(
+                ident,
+                FieldValueArg::Profile(Box::new(profile)),
+            ));
+        }
+        let mut value = FieldValueArg::from_ident(&ident)?;
+        // Options of the iterator are given by `into_iter(...)`
+        if let (FieldValueArg::Iter(options), true) = (&mut value, input.peek(syn::token::Paren)) {
+            let content;
+            syn::parenthesized!(content in input);
+            *options = Some(Box::new(content.parse()?));
+        }
+        Ok(Self::Value(ident, value))
+    }
+}
+
+impl FieldArgGeneric {
+    fn ident(&self) -> &syn::Ident {
+        match self {
+            Self::Value(ident, _) | Self::KeyValue(ident, _) => ident,
         }
-        Ok(Self::Value(FieldValueArg::from_ident(&ident)?))
     }
 }
 
 pub enum StructValueArg {
-    None,
+    EpsilonStruct,
+    NanEqual,
+    InfEqual,
+    Profile(Box<Profile>),
+    Also(Box<ComputedProperty>),
+    Transparent,
+    Mismatches(Option<syn::Ident>),
 }
 
 impl StructValueArg {
-    pub fn from_ident(_ident: &syn::Ident) -> syn::Result<Self> {
-        // match ident.to_string().as_str() {
-        //     _ => Ok(Self::None),
-        //     // _ => Err(syn::Error::new(ident.span(), "Not a valid value")),
-        // }
-        Ok(Self::None)
+    pub fn from_ident(ident: &syn::Ident) -> syn::Result<Self> {
+        match ident.to_string().as_str() {
+            "epsilon_struct" => Ok(Self::EpsilonStruct),
+            "nan_equal" => Ok(Self::NanEqual),
+            "inf_equal" => Ok(Self::InfEqual),
+            "transparent" => Ok(Self::Transparent),
+            "mismatches" => Ok(Self::Mismatches(None)),
+            _ => Err(unknown_arg_error(
+                ident,
+                STRUCT_VALUE_ARGS,
+                STRUCT_KEY_VALUE_ARGS,
+            )),
+        }
     }
 }
 
 pub enum StructKeyValueArg {
-    EpsilonType(syn::Type),
+    EpsilonType(Box<syn::Type>),
     DefaultEpsilon(syn::Expr),
     DefaultMaxRelative(syn::Expr),
+    DefaultMaxUlps(syn::Expr),
+    Rhs(Box<syn::Type>),
+    Bound(Vec<syn::WherePredicate>),
+    Remote(syn::Path),
+    Via(Box<syn::Type>),
+    Convert(syn::Expr),
 }
 
 impl StructKeyValueArg {
     pub fn parse_value(keyword: &syn::Ident, input: syn::parse::ParseStream) -> syn::Result<Self> {
         match keyword.to_string().as_str() {
-            "epsilon_type" => Ok(Self::EpsilonType(input.parse()?)),
+            "epsilon_type" => Ok(Self::EpsilonType(Box::new(input.parse()?))),
             "default_epsilon" => Ok(Self::DefaultEpsilon(input.parse()?)),
             "default_max_relative" => Ok(Self::DefaultMaxRelative(input.parse()?)),
-            _ => Err(syn::Error::new(keyword.span(), "Not a valid keyword")),
+            "default_max_ulps" => Ok(Self::DefaultMaxUlps(input.parse()?)),
+            "rhs" => Ok(Self::Rhs(Box::new(input.parse()?))),
+            "bound" => Ok(Self::Bound(parse_bound(input)?)),
+            "remote" => {
+                let path: syn::LitStr = input.parse()?;
+                match path.parse() {
+                    Ok(path) => Ok(Self::Remote(path)),
+                    Err(_) => Err(syn::Error::new(
+                        path.span(),
+                        format!("the remote type `{}` is not a valid path", path.value()),
+                    )),
+                }
+            }
+            "via" => Ok(Self::Via(Box::new(input.parse()?))),
+            "convert" => Ok(Self::Convert(input.parse()?)),
+            _ => Err(unknown_arg_error(
+                keyword,
+                STRUCT_VALUE_ARGS,
+                STRUCT_KEY_VALUE_ARGS,
+            )),
         }
     }
 }
 
 pub enum StructArgGeneric {
-    Value(StructValueArg),
-    KeyValue(StructKeyValueArg),
+    Value(syn::Ident, StructValueArg),
+    KeyValue(syn::Ident, StructKeyValueArg),
+}
+
+impl StructArgGeneric {
+    fn ident(&self) -> &syn::Ident {
+        match self {
+            Self::Value(ident, _) | Self::KeyValue(ident, _) => ident,
+        }
+    }
 }
 
 impl syn::parse::Parse for StructArgGeneric {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let ident: syn::Ident = input.parse()?;
-        if input.peek(syn::Token![=]) {
+        // The report takes an optional name for its entries by `mismatches = Name`
+        if ident == "mismatches" {
+            let mut name = None;
+            if input.peek(syn::Token![=]) {
+                let _: syn::Token![=] = input.parse()?;
+                name = Some(input.parse()?);
+            }
+            return Ok(Self::Value(ident, StructValueArg::Mismatches(name)));
+        }
+        let has_value = input.peek(syn::Token![=]);
+        check_arg_form(&ident, has_value, STRUCT_VALUE_ARGS, STRUCT_KEY_VALUE_ARGS)?;
+        if has_value {
             // Now we know that this will be a key-value pair
             let keyword = ident;
             let _: syn::Token![=] = input.parse()?;
             let key_value_arg = StructKeyValueArg::parse_value(&keyword, input)?;
-            return Ok(Self::KeyValue(key_value_arg));
+            return Ok(Self::KeyValue(keyword, key_value_arg));
         }
         // Otherwise we know that it is a value
-        Ok(Self::Value(StructValueArg::from_ident(&ident)?))
+        if ident == "profile" {
+            let profile = Profile::parse_with_options(input, STRUCT_PROFILE_OPTIONS)?;
+            return Ok(Self::Value(
+                ident,
+                StructValueArg::Profile(Box::new(profile)),
+            ));
+        }
+        if ident == "also" {
+            let property = input.parse()?;
+            return Ok(Self::Value(ident, StructValueArg::Also(Box::new(property))));
+        }
+        let value = StructValueArg::from_ident(&ident)?;
+        Ok(Self::Value(ident, value))
     }
 }
 
@@ -200,27 +1103,115 @@ impl StructArgs {
         let mut epsilon_type = None;
         let mut default_epsilon_value = None;
         let mut default_max_relative_value = None;
+        let mut default_max_ulps_value = None;
+        let mut rhs = None;
+        let mut epsilon_struct = None;
+        let mut nan_equal = None;
+        let mut inf_equal = None;
+        let mut bound = None;
+        let mut profiles = Vec::new();
+        let mut also = Vec::new();
+        let mut remote = None;
+        let mut transparent = None;
+        let mut via = None;
+        let mut convert = None;
+        let mut mismatches = None;
+        let mut seen = SeenArgs::default();
         for attribute in attributes.iter() {
-            match attribute.parse_args() {
-                Ok(StructArgGeneric::Value(StructValueArg::None)) => (),
-                Ok(StructArgGeneric::KeyValue(StructKeyValueArg::EpsilonType(epsilon_ty))) => {
-                    epsilon_type = Some(epsilon_ty)
-                }
-                Ok(StructArgGeneric::KeyValue(StructKeyValueArg::DefaultEpsilon(default_eps))) => {
-                    default_epsilon_value = Some(default_eps)
-                }
-                Ok(StructArgGeneric::KeyValue(StructKeyValueArg::DefaultMaxRelative(
-                    default_max_rel,
-                ))) => {
-                    default_max_relative_value = Some(default_max_rel);
+            // Other attributes such as doc comments or derives are not of our concern
+            if !attribute.path().is_ident("approx") {
+                continue;
+            }
+            let args = attribute.parse_args_with(
+                syn::punctuated::Punctuated::<StructArgGeneric, syn::Token![,]>::parse_terminated,
+            )?;
+            for arg in args {
+                seen.insert(arg.ident())?;
+                match arg {
+                    StructArgGeneric::Value(_, StructValueArg::EpsilonStruct) => {
+                        epsilon_struct = Some(true)
+                    }
+                    StructArgGeneric::Value(_, StructValueArg::NanEqual) => nan_equal = Some(true),
+                    StructArgGeneric::Value(_, StructValueArg::InfEqual) => inf_equal = Some(true),
+                    StructArgGeneric::Value(_, StructValueArg::Transparent) => {
+                        transparent = Some(true)
+                    }
+                    StructArgGeneric::Value(_, StructValueArg::Mismatches(name)) => {
+                        mismatches = Some(name)
+                    }
+                    StructArgGeneric::Value(_, StructValueArg::Profile(profile)) => {
+                        profile.check_unique(&profiles)?;
+                        profiles.push(*profile)
+                    }
+                    StructArgGeneric::Value(_, StructValueArg::Also(property)) => {
+                        if also
+                            .iter()
+                            .any(|x: &ComputedProperty| x.name == property.name)
+                        {
+                            return Err(syn::Error::new(
+                                property.name.span(),
+                                format!(
+                                    "the computed property `{}` is specified more than once",
+                                    property.name
+                                ),
+                            ));
+                        }
+                        also.push(*property)
+                    }
+                    StructArgGeneric::KeyValue(_, StructKeyValueArg::EpsilonType(epsilon_ty)) => {
+                        epsilon_type = Some(*epsilon_ty)
+                    }
+                    StructArgGeneric::KeyValue(
+                        _,
+                        StructKeyValueArg::DefaultEpsilon(default_eps),
+                    ) => default_epsilon_value = Some(default_eps),
+                    StructArgGeneric::KeyValue(
+                        _,
+                        StructKeyValueArg::DefaultMaxRelative(default_max_rel),
+                    ) => {
+                        default_max_relative_value = Some(default_max_rel);
+                    }
+                    StructArgGeneric::KeyValue(
+                        _,
+                        StructKeyValueArg::DefaultMaxUlps(default_max_ulps),
+                    ) => {
+                        default_max_ulps_value = Some(default_max_ulps);
+                    }
+                    StructArgGeneric::KeyValue(_, StructKeyValueArg::Rhs(rhs_type)) => {
+                        rhs = Some(*rhs_type)
+                    }
+                    StructArgGeneric::KeyValue(_, StructKeyValueArg::Bound(predicates)) => {
+                        bound = Some(predicates)
+                    }
+                    StructArgGeneric::KeyValue(_, StructKeyValueArg::Remote(path)) => {
+                        remote = Some(path)
+                    }
+                    StructArgGeneric::KeyValue(_, StructKeyValueArg::Via(ty)) => via = Some(*ty),
+                    StructArgGeneric::KeyValue(_, StructKeyValueArg::Convert(expr)) => {
+                        convert = Some(expr)
+                    }
                 }
-                Err(_) => {}
             }
         }
+        seen.check_conflicts(STRUCT_CONFLICTS)?;
+        seen.check_requirements(STRUCT_REQUIREMENTS)?;
         Ok(Self {
             epsilon_type,
             default_epsilon_value,
             default_max_relative_value,
+            default_max_ulps_value,
+            rhs,
+            epsilon_struct,
+            nan_equal,
+            inf_equal,
+            bound,
+            profiles,
+            also,
+            remote,
+            transparent,
+            via,
+            convert,
+            mismatches,
         })
     }
 }
@@ -235,46 +1226,334 @@ impl FieldArgs {
         let mut cast_strategy = None;
         let mut epsilon_static_value = None;
         let mut max_relative_static_value = None;
+        let mut max_ulps_static_value = None;
+        let mut skip_if = None;
+        let mut compare_if = None;
+        let mut epsilon_value = None;
+        let mut max_relative_value = None;
+        let mut max_ulps_mapping = None;
         let mut iter = None;
+        let mut iter_options: Option<IterOptions> = None;
+        let mut unordered = None;
+        let mut norm = None;
+        let mut compare = None;
+        let mut periodic = None;
+        let mut nan_equal = None;
+        let mut inf_equal = None;
+        let mut map_keys = None;
+        let mut with_module = None;
+        let mut compare_with = None;
+        let mut bound = None;
+        let mut groups = Vec::new();
+        let mut profiles = Vec::new();
+        let mut seen = SeenArgs::default();
         for attribute in attributes.iter() {
             // Only do anything if approx is specified
             if attribute.path().is_ident("approx") {
-                let arg: FieldArgGeneric = attribute.parse_args()?;
-                match arg {
-                    FieldArgGeneric::Value(FieldValueArg::Skip) => skip = Some(true),
-                    FieldArgGeneric::Value(FieldValueArg::CastStrategy(strategy)) => {
-                        cast_strategy = Some(strategy)
-                    }
-                    FieldArgGeneric::Value(FieldValueArg::Equal) => set_equal = Some(true),
-                    FieldArgGeneric::Value(FieldValueArg::Iter) => iter = Some(true),
-                    FieldArgGeneric::KeyValue(FieldKeyValueArg::EpsilonStatic(epsilon_static)) => {
-                        epsilon_static_value = epsilon_static;
-                    }
-                    FieldArgGeneric::KeyValue(FieldKeyValueArg::MaxRelativeStatic(
-                        max_rel_static,
-                    )) => {
-                        max_relative_static_value = max_rel_static;
-                    }
-                    FieldArgGeneric::KeyValue(FieldKeyValueArg::Mapping(expr)) => mapping = expr,
-                    FieldArgGeneric::KeyValue(FieldKeyValueArg::EpsilonMapping(expr)) => {
-                        epsilon_mapping = expr
-                    }
-                    FieldArgGeneric::KeyValue(FieldKeyValueArg::MaxRelativeMapping(expr)) => {
-                        max_relative_mapping = expr
+                let args = attribute.parse_args_with(
+                    syn::punctuated::Punctuated::<FieldArgGeneric, syn::Token![,]>::parse_terminated,
+                )?;
+                for arg in args {
+                    seen.insert(arg.ident())?;
+                    match arg {
+                        FieldArgGeneric::Value(_, FieldValueArg::Skip) => skip = Some(true),
+                        FieldArgGeneric::Value(_, FieldValueArg::CastStrategy(strategy)) => {
+                            cast_strategy = Some(*strategy)
+                        }
+                        FieldArgGeneric::Value(_, FieldValueArg::Equal) => set_equal = Some(true),
+                        FieldArgGeneric::Value(_, FieldValueArg::Iter(options)) => {
+                            iter = Some(true);
+                            iter_options = options.map(|options| *options);
+                        }
+                        FieldArgGeneric::Value(_, FieldValueArg::Unordered) => {
+                            unordered = Some(true)
+                        }
+                        FieldArgGeneric::Value(_, FieldValueArg::MapKeys) => map_keys = Some(true),
+                        FieldArgGeneric::Value(_, FieldValueArg::NanEqual) => {
+                            nan_equal = Some(true)
+                        }
+                        FieldArgGeneric::Value(_, FieldValueArg::InfEqual) => {
+                            inf_equal = Some(true)
+                        }
+                        FieldArgGeneric::Value(_, FieldValueArg::Profile(profile)) => {
+                            profile.check_unique(&profiles)?;
+                            profiles.push(*profile)
+                        }
+                        FieldArgGeneric::KeyValue(
+                            _,
+                            FieldKeyValueArg::EpsilonStatic(epsilon_static),
+                        ) => {
+                            epsilon_static_value = epsilon_static;
+                        }
+                        FieldArgGeneric::KeyValue(
+                            _,
+                            FieldKeyValueArg::MaxRelativeStatic(max_rel_static),
+                        ) => {
+                            max_relative_static_value = max_rel_static;
+                        }
+                        FieldArgGeneric::KeyValue(
+                            _,
+                            FieldKeyValueArg::MaxUlpsStatic(max_ulps_static),
+                        ) => {
+                            max_ulps_static_value = max_ulps_static;
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::SkipIf(expr)) => {
+                            skip_if = Some(expr)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::CompareIf(expr)) => {
+                            compare_if = Some(expr)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::Epsilon(expr)) => {
+                            epsilon_value = Some(expr)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::MaxRelative(expr)) => {
+                            max_relative_value = Some(expr)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::Mapping(expr)) => {
+                            mapping = expr
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::EpsilonMapping(expr)) => {
+                            epsilon_mapping = expr
+                        }
+                        FieldArgGeneric::KeyValue(
+                            _,
+                            FieldKeyValueArg::MaxRelativeMapping(expr),
+                        ) => max_relative_mapping = expr,
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::MaxUlpsMapping(expr)) => {
+                            max_ulps_mapping = expr
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::With(path)) => {
+                            with_module = Some(path)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::Periodic(expr)) => {
+                            periodic = Some(expr)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::Norm(value)) => {
+                            norm = Some(value)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::Compare(value)) => {
+                            compare = Some(value)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::CompareWith(expr)) => {
+                            compare_with = Some(expr)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::Bound(predicates)) => {
+                            bound = Some(predicates)
+                        }
+                        FieldArgGeneric::KeyValue(_, FieldKeyValueArg::Group(group)) => {
+                            groups.push(group)
+                        }
                     }
                 }
             }
         }
-        Ok(Self {
+        let args = Self {
             skip,
+            skip_if,
+            compare_if,
             set_equal,
             cast_strategy,
             epsilon_static_value,
             max_relative_static_value,
+            max_ulps_static_value,
+            epsilon_value,
+            max_relative_value,
             mapping,
             epsilon_mapping,
             max_relative_mapping,
+            max_ulps_mapping,
             use_iterator: iter,
-        })
+            iter_options,
+            unordered,
+            norm,
+            compare,
+            periodic,
+            nan_equal,
+            inf_equal,
+            use_map_keys: map_keys,
+            with_module,
+            compare_with,
+            bound,
+            groups,
+            profiles,
+            seen,
+        };
+        args.check_combinations()?;
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The message of the error returned for the given field attributes
+    fn field_error(attrs: &[syn::Attribute]) -> String {
+        match FieldArgs::from_attrs(attrs) {
+            Ok(_) => String::from("no error"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn field_conflicts() {
+        let cases: Vec<(syn::Attribute, &str)> = vec![
+            (
+                syn::parse_quote!(#[approx(equal, map = |x| Some(*x))]),
+                "`equal` can not be combined with `map`",
+            ),
+            (
+                syn::parse_quote!(#[approx(into_iter, equal)]),
+                "`equal` can not be combined with `into_iter`",
+            ),
+            (
+                syn::parse_quote!(#[approx(equal, map_keys)]),
+                "`equal` can not be combined with `map_keys`",
+            ),
+            (
+                syn::parse_quote!(#[approx(cast_field, equal)]),
+                "`equal` can not be combined with `cast_field`",
+            ),
+            (
+                syn::parse_quote!(#[approx(equal, cast_value = into)]),
+                "`equal` can not be combined with `cast_value`",
+            ),
+            (
+                syn::parse_quote!(#[approx(map = |x| Some(*x), into_iter)]),
+                "`map` can not be combined with `into_iter`",
+            ),
+            (
+                syn::parse_quote!(#[approx(map_keys, map = |x| Some(*x))]),
+                "`map` can not be combined with `map_keys`",
+            ),
+        ];
+        for (attr, message) in cases {
+            assert_eq!(field_error(&[attr]), message);
+        }
+    }
+
+    #[test]
+    fn field_validation() {
+        let cases: Vec<(syn::Attribute, &str)> = vec![
+            (
+                syn::parse_quote!(#[approx(skip, equal)]),
+                "`skip` can not be combined with `equal`",
+            ),
+            (
+                syn::parse_quote!(#[approx(equal, equal)]),
+                "`equal` is specified more than once",
+            ),
+            (
+                syn::parse_quote!(#[approx(static_epsilo = 0.1)]),
+                "unknown approx attribute `static_epsilo`, did you mean `static_epsilon`?",
+            ),
+            (
+                syn::parse_quote!(#[approx(map)]),
+                "`map` requires a value: `map = ...`",
+            ),
+            (
+                syn::parse_quote!(#[approx(profile(fast, static_epsilon = 0.1))]),
+                "expected the tolerances of the profile: `profile(fast(...))`",
+            ),
+        ];
+        for (attr, message) in cases {
+            assert_eq!(field_error(&[attr]), message);
+        }
+    }
+
+    #[test]
+    fn duplicate_field_profiles() {
+        let attrs: Vec<syn::Attribute> = vec![
+            syn::parse_quote!(#[approx(profile(fast(static_epsilon = 1.0)))]),
+            syn::parse_quote!(#[approx(profile(fast(static_epsilon = 2.0)))]),
+        ];
+        assert_eq!(
+            field_error(&attrs),
+            "the profile `fast` is specified more than once"
+        );
+    }
+
+    #[test]
+    fn profiles_with_epsilon_struct() {
+        let attrs: Vec<syn::Attribute> = vec![syn::parse_quote!(
+            #[approx(epsilon_struct, profile(fast(default_epsilon = 1.0)))]
+        )];
+        let message = match StructArgs::from_attrs(&attrs) {
+            Ok(_) => String::from("no error"),
+            Err(error) => error.to_string(),
+        };
+        assert_eq!(
+            message,
+            "`profile` can not be combined with `epsilon_struct`"
+        );
+    }
+
+    #[test]
+    fn epsilon_type_with_epsilon_struct() {
+        let attrs: Vec<syn::Attribute> =
+            vec![syn::parse_quote!(#[approx(epsilon_struct, epsilon_type = f32)])];
+        let message = match StructArgs::from_attrs(&attrs) {
+            Ok(_) => String::from("no error"),
+            Err(error) => error.to_string(),
+        };
+        assert_eq!(
+            message,
+            "`epsilon_type` can not be combined with `epsilon_struct`"
+        );
+    }
+
+    #[test]
+    fn field_conflicts_with_variant() {
+        let parse = |attr: syn::Attribute| match FieldArgs::from_attrs(&[attr]) {
+            Ok(args) => args,
+            Err(error) => panic!("{error}"),
+        };
+        let cases: Vec<(syn::Attribute, syn::Attribute, &str)> = vec![
+            (
+                syn::parse_quote!(#[approx(equal)]),
+                syn::parse_quote!(#[approx(map = |x: &f32| Some(2.0 * x))]),
+                "`equal` can not be combined with `map`",
+            ),
+            (
+                syn::parse_quote!(#[approx(skip)]),
+                syn::parse_quote!(#[approx(into_iter)]),
+                "`skip` can not be combined with `into_iter`",
+            ),
+            (
+                syn::parse_quote!(#[approx(into_iter(length = shortest))]),
+                syn::parse_quote!(#[approx(into_iter, unordered)]),
+                "`length = shortest` can not be combined with `unordered`",
+            ),
+        ];
+        for (variant, field, message) in cases {
+            let variant = parse(variant);
+            let mut field = parse(field);
+            let result = match field.patch_if_not_exists(&variant) {
+                Ok(_) => String::from("no error"),
+                Err(error) => error.to_string(),
+            };
+            assert_eq!(result, message);
+        }
+
+        // Arguments of the field override the same arguments of the variant
+        let variant = parse(syn::parse_quote!(#[approx(static_epsilon = 0.1, into_iter)]));
+        let mut field =
+            parse(syn::parse_quote!(#[approx(static_epsilon = 0.2, unordered, into_iter)]));
+        assert!(field.patch_if_not_exists(&variant).is_ok());
+    }
+
+    /// Previously, `equal` silently took precedence over `map` when both were specified
+    #[test]
+    fn equal_and_mapping_are_rejected() {
+        let equal: syn::Attribute = syn::parse_quote!(#[approx(equal)]);
+        let map: syn::Attribute = syn::parse_quote!(#[approx(map = |x: &f32| Some(2.0 * x))]);
+        assert_eq!(
+            field_error(&[equal.clone(), map.clone()]),
+            "`equal` can not be combined with `map`"
+        );
+        assert_eq!(
+            field_error(&[map, equal]),
+            "`equal` can not be combined with `map`"
+        );
     }
 }
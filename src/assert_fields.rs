@@ -0,0 +1,270 @@
+use crate::base_types::*;
+
+/// Arguments of the `assert_fields_*` macros: `left, right, epsilon = ..., max_relative = ...`
+pub struct AssertFieldsArgs {
+    pub left: syn::Expr,
+    pub right: syn::Expr,
+    pub epsilon: Option<syn::Expr>,
+    pub max_relative: Option<syn::Expr>,
+}
+
+impl syn::parse::Parse for AssertFieldsArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let left = input.parse()?;
+        input.parse::<syn::Token![,]>()?;
+        let right = input.parse()?;
+        let mut args = Self {
+            left,
+            right,
+            epsilon: None,
+            max_relative: None,
+        };
+        while !input.is_empty() {
+            input.parse::<syn::Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let keyword: syn::Ident = input.parse()?;
+            input.parse::<syn::Token![=]>()?;
+            let value: syn::Expr = input.parse()?;
+            let slot = match keyword.to_string().as_str() {
+                "epsilon" => &mut args.epsilon,
+                "max_relative" => &mut args.max_relative,
+                _ => {
+                    return Err(syn::Error::new(
+                        keyword.span(),
+                        "expected `epsilon = ...` or `max_relative = ...`",
+                    ))
+                }
+            };
+            if slot.is_some() {
+                return Err(syn::Error::new(
+                    keyword.span(),
+                    format!("{keyword} is specified more than once"),
+                ));
+            }
+            *slot = Some(value);
+        }
+        Ok(args)
+    }
+}
+
+impl AssertFieldsArgs {
+    /// Generates the assertion which panics with a table of all mismatching fields.
+    ///
+    /// The table is printed by a `#[track_caller]` function such that the panic points to the
+    /// invocation of the macro.
+    /// The report is picked up by method resolution: the inherent method generated by
+    /// `#[approx(mismatches)]` takes precedence over the fallback trait which is implemented for
+    /// all types and reports both values formatted with [Debug].
+    pub fn implement_assert_fields(
+        &self,
+        approx_trait: ApproxTrait,
+        macro_name: &str,
+    ) -> syn::Result<proc_macro2::TokenStream> {
+        let Self {
+            left,
+            right,
+            epsilon,
+            max_relative,
+        } = self;
+        let epsilon = epsilon
+            .as_ref()
+            .map(|x| quote::quote!(#x))
+            .unwrap_or_else(|| quote::quote!(__approx_default_epsilon(left_val, right_val)));
+        let (check, mismatches, fallback, defaults) = match approx_trait {
+            ApproxTrait::Relative => {
+                let max_relative = max_relative
+                    .as_ref()
+                    .map(|x| quote::quote!(#x))
+                    .unwrap_or_else(|| {
+                        quote::quote!(__approx_default_max_relative(left_val, right_val))
+                    });
+                (
+                    quote::quote!(#ApproxName::RelativeEq::relative_eq(
+                        left_val,
+                        right_val,
+                        epsilon.clone(),
+                        max_relative.clone()
+                    )),
+                    quote::quote!(left_val.approx_relative_mismatches(
+                        right_val,
+                        epsilon,
+                        max_relative
+                    )),
+                    quote::quote!(
+                        fn approx_relative_mismatches<O, E, M>(
+                            &self,
+                            other: &O,
+                            epsilon: E,
+                            max_relative: M,
+                        ) -> Vec<__ApproxValues>
+                        where
+                            O: core::fmt::Debug + ?Sized,
+                            E: core::fmt::Debug,
+                            M: core::fmt::Debug,
+                        {
+                            vec![__ApproxValues {
+                                path: String::from("(value)"),
+                                left: format!("{:?}", self),
+                                right: format!("{:?}", other),
+                                diff: None,
+                                epsilon: Some(format!("{:?}", epsilon)),
+                                max_relative: Some(format!("{:?}", max_relative)),
+                            }]
+                        }
+                    ),
+                    quote::quote!(
+                        let max_relative = #max_relative;
+                    ),
+                )
+            }
+            _ => {
+                if let Some(max_relative) = max_relative {
+                    use syn::spanned::Spanned;
+                    return Err(syn::Error::new(
+                        max_relative.span(),
+                        format!("{macro_name}! does not accept max_relative"),
+                    ));
+                }
+                (
+                    quote::quote!(#ApproxName::AbsDiffEq::abs_diff_eq(
+                        left_val,
+                        right_val,
+                        epsilon.clone()
+                    )),
+                    quote::quote!(left_val.approx_mismatches(right_val, epsilon)),
+                    quote::quote!(
+                        fn approx_mismatches<O, E>(
+                            &self,
+                            other: &O,
+                            epsilon: E,
+                        ) -> Vec<__ApproxValues>
+                        where
+                            O: core::fmt::Debug + ?Sized,
+                            E: core::fmt::Debug,
+                        {
+                            vec![__ApproxValues {
+                                path: String::from("(value)"),
+                                left: format!("{:?}", self),
+                                right: format!("{:?}", other),
+                                diff: None,
+                                epsilon: Some(format!("{:?}", epsilon)),
+                                max_relative: None,
+                            }]
+                        }
+                    ),
+                    quote::quote!(),
+                )
+            }
+        };
+        let header = format!(
+            "assertion failed: `{}!({}, {})`",
+            macro_name,
+            quote::quote!(#left),
+            quote::quote!(#right)
+        );
+
+        Ok(quote::quote!(
+            match (&(#left), &(#right)) {
+                (left_val, right_val) => {
+                    #[allow(unused)]
+                    fn __approx_default_epsilon<A, B>(_: &A, _: &B) -> A::Epsilon
+                    where
+                        A: #ApproxName::AbsDiffEq<B> + ?Sized,
+                        B: ?Sized,
+                    {
+                        A::default_epsilon()
+                    }
+                    #[allow(unused)]
+                    fn __approx_default_max_relative<A, B>(_: &A, _: &B) -> A::Epsilon
+                    where
+                        A: #ApproxName::RelativeEq<B> + ?Sized,
+                        B: ?Sized,
+                    {
+                        A::default_max_relative()
+                    }
+                    // Entry of the report for types which do not generate their own
+                    #[allow(unused)]
+                    struct __ApproxValues {
+                        path: String,
+                        left: String,
+                        right: String,
+                        diff: Option<String>,
+                        epsilon: Option<String>,
+                        max_relative: Option<String>,
+                    }
+                    #[allow(unused)]
+                    trait __ApproxMismatchesFallback: core::fmt::Debug {
+                        #fallback
+                    }
+                    impl<T: core::fmt::Debug + ?Sized> __ApproxMismatchesFallback for T {}
+                    #[cold]
+                    #[track_caller]
+                    fn __approx_fail(header: &str, rows: Vec<[String; 5]>) -> ! {
+                        let titles = [
+                            String::from("path"),
+                            String::from("left"),
+                            String::from("right"),
+                            String::from("|diff|"),
+                            String::from("tolerance"),
+                        ];
+                        let mut widths = [0usize; 5];
+                        for row in core::iter::once(&titles).chain(rows.iter()) {
+                            for (width, cell) in widths.iter_mut().zip(row.iter()) {
+                                *width = (*width).max(cell.chars().count());
+                            }
+                        }
+                        let format_row = |row: &[String; 5]| {
+                            row.iter()
+                                .zip(widths.iter())
+                                .map(|(cell, width)| format!("{:<width$}", cell, width = *width))
+                                .collect::<Vec<_>>()
+                                .join(" | ")
+                        };
+                        let separator = widths
+                            .iter()
+                            .map(|width| "-".repeat(*width))
+                            .collect::<Vec<_>>()
+                            .join("-+-");
+                        let mut message = format!("{}\n\n{}\n{}", header, format_row(&titles), separator);
+                        for row in rows.iter() {
+                            message.push('\n');
+                            message.push_str(&format_row(row));
+                        }
+                        panic!("{}\n", message)
+                    }
+
+                    let epsilon = #epsilon;
+                    #defaults
+                    if !#check {
+                        let rows = #mismatches
+                            .into_iter()
+                            .map(|m| {
+                                let tolerance = match (m.epsilon, m.max_relative) {
+                                    (Some(epsilon), Some(max_relative)) => {
+                                        format!("{} (max_relative {})", epsilon, max_relative)
+                                    }
+                                    (Some(epsilon), None) => epsilon,
+                                    _ => String::from("-"),
+                                };
+                                let path = match m.path.is_empty() {
+                                    true => String::from("(variant)"),
+                                    false => m.path,
+                                };
+                                [
+                                    path,
+                                    m.left,
+                                    m.right,
+                                    m.diff.unwrap_or_else(|| String::from("-")),
+                                    tolerance,
+                                ]
+                            })
+                            .collect();
+                        __approx_fail(#header, rows);
+                    }
+                }
+            }
+        ))
+    }
+}
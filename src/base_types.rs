@@ -27,6 +27,24 @@ impl quote::ToTokens for ApproxName {
     }
 }
 
+/// The traits of the approx crate which can be derived
+#[derive(Clone, Copy, PartialEq)]
+pub enum ApproxTrait {
+    Abs,
+    Relative,
+    Ulps,
+}
+
+impl quote::ToTokens for ApproxTrait {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        match self {
+            ApproxTrait::Abs => tokens.extend(quote::quote!(#ApproxName::AbsDiffEq)),
+            ApproxTrait::Relative => tokens.extend(quote::quote!(#ApproxName::RelativeEq)),
+            ApproxTrait::Ulps => tokens.extend(quote::quote!(#ApproxName::UlpsEq)),
+        }
+    }
+}
+
 pub enum BaseType {
     Struct {
         item_struct: syn::ItemStruct,
@@ -75,7 +93,7 @@ impl syn::parse::Parse for BaseType {
                         .iter()
                         .map(|f| {
                             let mut fwa = FieldWithArgs::from_field(f)?;
-                            fwa.args.patch_if_not_exists(&args);
+                            fwa.args.patch_if_not_exists(&args)?;
                             Ok(fwa)
                         })
                         .collect::<syn::Result<Vec<_>>>()?;
@@ -130,6 +148,40 @@ impl BaseType {
         }
     }
 
+    pub fn vis(&self) -> &syn::Visibility {
+        match self {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => &item_struct.vis,
+            #[allow(unused)]
+            BaseType::Enum {
+                item_enum,
+                variants_with_args,
+            } => &item_enum.vis,
+        }
+    }
+
+    /// All fields of the struct or of every enum variant
+    pub fn fields_mut(&mut self) -> Vec<&mut FieldWithArgs> {
+        match self {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => fields_with_args.iter_mut().collect(),
+            #[allow(unused)]
+            BaseType::Enum {
+                item_enum,
+                variants_with_args,
+            } => variants_with_args
+                .iter_mut()
+                .flat_map(|v| v.fields_with_args.iter_mut())
+                .collect(),
+        }
+    }
+
     pub fn ident(&self) -> &syn::Ident {
         match self {
             #[allow(unused)]
@@ -146,6 +198,33 @@ impl BaseType {
     }
 }
 
+/// The value type `V` of a map such as `HashMap<K, V>` or `BTreeMap<K, V>`
+pub fn get_map_value_type(ty: &syn::Type) -> Option<&syn::Type> {
+    match ty {
+        syn::Type::Path(type_path) => match &type_path.path.segments.last()?.arguments {
+            syn::PathArguments::AngleBracketed(arguments) => arguments
+                .args
+                .iter()
+                .filter_map(|argument| match argument {
+                    syn::GenericArgument::Type(ty) => Some(ty),
+                    _ => None,
+                })
+                .nth(1),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Comparison of a field by user-provided functions
+#[derive(Debug)]
+pub enum CustomComparison {
+    /// `#[approx(with = module)]` calls the functions of the module named after the trait methods
+    Module(proc_macro2::TokenStream),
+    /// `#[approx(compare_with = function)]` calls `function(&a, &b, epsilon)` for every trait
+    Function(proc_macro2::TokenStream),
+}
+
 #[derive(Debug)]
 pub struct FieldFormatted {
     pub base_type: proc_macro2::TokenStream,
@@ -153,8 +232,109 @@ pub struct FieldFormatted {
     pub other_field: proc_macro2::TokenStream,
     pub epsilon: proc_macro2::TokenStream,
     pub max_relative: proc_macro2::TokenStream,
+    pub max_ulps: proc_macro2::TokenStream,
     pub mapping: Option<proc_macro2::TokenStream>,
     pub set_equal: bool,
     // If this is Some type, we should be matching for this type
     pub use_iterator: bool,
+    pub use_map_keys: bool,
+    pub compare_with: Option<CustomComparison>,
+    /// Expression which is true if the field should not be compared
+    pub skip_condition: Option<proc_macro2::TokenStream>,
+    /// The unconverted fields if the cast by `try_into` may fail
+    pub fallible_cast: Option<(proc_macro2::TokenStream, proc_macro2::TokenStream)>,
+}
+
+/// Label of the block which is left with `false` if a conversion by `try_into` fails
+const CAST_LABEL: &str = "'__approx_cast";
+
+/// Converts a value by the method given by `#[approx(cast_field = ...)]` or
+/// `#[approx(cast_value = ...)]`.
+///
+/// Failed conversions by `try_into` break out of the block created by [with_fallible_cast].
+pub fn get_cast_conversion(
+    value: proc_macro2::TokenStream,
+    method: CastMethod,
+    target: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let label = syn::Lifetime::new(CAST_LABEL, proc_macro2::Span::call_site());
+    match method {
+        CastMethod::As => quote::quote!((#value.clone() as #target)),
+        CastMethod::Into => quote::quote!(core::convert::Into::<#target>::into(#value.clone())),
+        CastMethod::TryInto => quote::quote!(
+            (match core::convert::TryInto::<#target>::try_into(#value.clone()) {
+                Ok(value) => value,
+                Err(_) => break #label false,
+            })
+        ),
+    }
+}
+
+/// Wraps the comparison of a single field into a block which evaluates to `false` if a
+/// conversion by `try_into` fails.
+pub fn with_fallible_cast(
+    fallible: bool,
+    check: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match fallible {
+        true => {
+            let label = syn::Lifetime::new(CAST_LABEL, proc_macro2::Span::call_site());
+            quote::quote!((#label: { #check }))
+        }
+        false => check,
+    }
+}
+
+/// Extends the comparison of a single field such that it succeeds if the field is skipped by
+/// `#[approx(skip_if = ...)]` or `#[approx(compare_if = ...)]`.
+pub fn with_skip_condition(
+    skip_condition: Option<proc_macro2::TokenStream>,
+    check: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match skip_condition {
+        Some(condition) => quote::quote!((#condition || #check)),
+        None => check,
+    }
+}
+
+impl EnumVariant {
+    /// Patterns which match this variant for `self` and `other` together with the identifiers
+    /// which are bound to the fields of both sides.
+    pub fn get_match_patterns(
+        &self,
+    ) -> (
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        Vec<(syn::Ident, syn::Ident)>,
+    ) {
+        use syn::spanned::Spanned;
+        let variant = &self.ident;
+        let gen_field_names = |var: &str| -> Vec<syn::Ident> {
+            self.fields_with_args
+                .iter()
+                .enumerate()
+                .map(|(n, field)| syn::Ident::new(&format!("{var}{n}"), field.ident.span()))
+                .collect()
+        };
+        let field_names1 = gen_field_names("x");
+        let field_names2 = gen_field_names("y");
+        let gen_pattern = |names: &[syn::Ident]| {
+            if self.fields_with_args.is_empty() {
+                quote::quote!(Self::#variant)
+            } else if self.fields_with_args[0].ident.is_some() {
+                let combos = names.iter().zip(&self.fields_with_args).map(|(name, fwa)| {
+                    let id = &fwa.ident;
+                    quote::quote!(#id: #name)
+                });
+                quote::quote!(Self::#variant { #(#combos),* })
+            } else {
+                quote::quote!(Self::#variant (#(#names),*))
+            }
+        };
+        (
+            gen_pattern(&field_names1),
+            gen_pattern(&field_names2),
+            field_names1.into_iter().zip(field_names2).collect(),
+        )
+    }
 }
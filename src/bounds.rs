@@ -0,0 +1,222 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::AbsDiffEqParser;
+
+/// Checks if any of the given identifiers occurs in the tokens of a type.
+fn tokens_contain_any(tokens: proc_macro2::TokenStream, idents: &[&syn::Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        proc_macro2::TokenTree::Ident(ident) => idents.iter().any(|id| **id == ident),
+        proc_macro2::TokenTree::Group(group) => tokens_contain_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
+impl AbsDiffEqParser {
+    /// Determines if the given type depends on any of the type parameters of the object.
+    fn uses_type_params(&self, ty: &syn::Type) -> bool {
+        let type_params: Vec<_> = self
+            .base_type
+            .generics()
+            .type_params()
+            .map(|param| &param.ident)
+            .collect();
+        !type_params.is_empty() && tokens_contain_any(quote::quote!(#ty), &type_params)
+    }
+
+    pub fn get_all_fields(&self) -> Vec<&FieldWithArgs> {
+        match &self.base_type {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => fields_with_args.iter().collect(),
+            #[allow(unused)]
+            BaseType::Enum {
+                item_enum,
+                variants_with_args,
+            } => variants_with_args
+                .iter()
+                .flat_map(|v| v.fields_with_args.iter())
+                .collect(),
+        }
+    }
+
+    /// Bounds which are required to compare a single field.
+    ///
+    /// The bounds specified by `#[approx(bound = "...")]` replace the inferred ones.
+    /// Fields which do not depend on any type parameter do not need bounds.
+    fn get_field_bounds(
+        &self,
+        field_with_args: &FieldWithArgs,
+        trait_bound: ApproxTrait,
+    ) -> Vec<proc_macro2::TokenStream> {
+        let args = &field_with_args.args;
+        if args.skip.unwrap_or(false) {
+            return Vec::new();
+        }
+        if let Some(bound) = &args.bound {
+            return bound.iter().map(|x| quote::quote!(#x)).collect();
+        }
+        let ty = &field_with_args.ty;
+        let trait_bound = args.compare.unwrap_or(trait_bound);
+        // When comparing against a different type, the type of the other field is not known.
+        if self.struct_args.rhs.is_some() || !self.uses_type_params(ty) {
+            return Vec::new();
+        }
+
+        let parent = self.get_epsilon_parent_type();
+        let epsilon_type = self.get_derived_epsilon_type();
+        // The tolerance only needs to match the epsilon type of the object if it is not
+        // specified or mapped for this field.
+        let same_epsilon = args.epsilon_static_value.is_none()
+            && args.epsilon_mapping.is_none()
+            && args.epsilon_value.is_none();
+        let trait_with_epsilon = match same_epsilon {
+            true => quote::quote!(#trait_bound<Epsilon = #epsilon_type>),
+            false => quote::quote!(#trait_bound),
+        };
+
+        if args.set_equal.unwrap_or(false) {
+            vec![quote::quote!(#ty: PartialEq)]
+        } else if args.with_module.is_some() || args.compare_with.is_some() {
+            // Custom comparisons are free to accept any type
+            Vec::new()
+        } else if args.mapping.is_some() {
+            Vec::new()
+        } else if args.use_map_keys.unwrap_or(false) {
+            // Bounds on the key type depend on the map and need to be specified manually
+            match get_map_value_type(ty) {
+                Some(value_type) if self.uses_type_params(value_type) => {
+                    vec![quote::quote!(#value_type: #trait_with_epsilon)]
+                }
+                _ => Vec::new(),
+            }
+        } else if args.use_iterator.unwrap_or(false) {
+            vec![
+                quote::quote!(for<'__approx> &'__approx #ty: core::iter::IntoIterator),
+                quote::quote!(
+                    for<'__approx> <&'__approx #ty as core::iter::IntoIterator>::Item:
+                        #trait_with_epsilon
+                ),
+            ]
+        } else {
+            let conversion = |target: &proc_macro2::TokenStream, method| match method {
+                CastMethod::TryInto => quote::quote!(core::convert::TryInto<#target>),
+                _ => quote::quote!(core::convert::Into<#target>),
+            };
+            match &args.cast_strategy {
+                // Casting with `as` is only possible for primitive types
+                Some(TypeCast::CastField(options)) if options.method == CastMethod::As => {
+                    Vec::new()
+                }
+                Some(TypeCast::CastField(options)) => {
+                    let target = match &options.to {
+                        Some(to) => quote::quote!(#to),
+                        None => parent.clone(),
+                    };
+                    let conversion = conversion(&target, options.method);
+                    let mut bounds = vec![quote::quote!(#ty: Clone + #conversion)];
+                    if target.to_string() != parent.to_string() {
+                        bounds.push(quote::quote!(#target: #trait_with_epsilon));
+                    }
+                    bounds
+                }
+                Some(TypeCast::CastValue(options)) if options.method == CastMethod::As => {
+                    vec![quote::quote!(#ty: #trait_bound)]
+                }
+                Some(TypeCast::CastValue(options)) => {
+                    let target = match &options.to {
+                        Some(to) => quote::quote!(#to),
+                        None => quote::quote!(#ty),
+                    };
+                    let conversion = conversion(&target, options.method);
+                    vec![
+                        quote::quote!(#ty: #trait_bound),
+                        quote::quote!(#epsilon_type: #conversion),
+                    ]
+                }
+                None if quote::quote!(#ty).to_string() == parent.to_string() => {
+                    vec![quote::quote!(#ty: #trait_bound)]
+                }
+                None => vec![quote::quote!(#ty: #trait_with_epsilon)],
+            }
+        }
+    }
+
+    /// Extends the where clause of the object by the bounds required by the derived trait.
+    ///
+    /// Bounds are inferred for the type which determines the epsilon type and every compared
+    /// field which depends on a type parameter.
+    /// The object attribute `#[approx(bound = "...")]` replaces all inferred bounds.
+    pub fn generate_where_clause(&self, trait_bound: ApproxTrait) -> proc_macro2::TokenStream {
+        let (_, _, where_clause) = self.base_type.generics().split_for_impl();
+        let mut predicates: Vec<proc_macro2::TokenStream> = where_clause
+            .map(|clause| {
+                clause
+                    .predicates
+                    .iter()
+                    .map(|x| quote::quote!(#x))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        match &self.struct_args.bound {
+            Some(bound) => predicates.extend(bound.iter().map(|x| quote::quote!(#x))),
+            None => {
+                // The derived traits require PartialEq as a supertrait which in turn may depend
+                // on type parameters that are not compared at all.
+                // Remote types are compared by functions which do not implement the traits.
+                if self.base_type.generics().type_params().next().is_some()
+                    && self.struct_args.remote.is_none()
+                {
+                    let rhs_type = self.get_rhs_type();
+                    predicates.push(quote::quote!(Self: PartialEq<#rhs_type>));
+                }
+                let parent = self.get_epsilon_parent_type();
+                let has_generic_parent = self
+                    .base_type
+                    .generics()
+                    .type_params()
+                    .any(|param| tokens_contain_any(parent.clone(), &[&param.ident]));
+                if has_generic_parent {
+                    let epsilon_type = self.get_derived_epsilon_type();
+                    predicates.push(quote::quote!(#parent: #trait_bound));
+                    predicates.push(quote::quote!(#epsilon_type: Clone));
+                    // Fields compared by a different method use the default tolerances of the
+                    // parent type
+                    let missing_defaults = [
+                        (
+                            ApproxTrait::Relative,
+                            self.struct_args.default_max_relative_value.is_none(),
+                        ),
+                        (
+                            ApproxTrait::Ulps,
+                            self.struct_args.default_max_ulps_value.is_none(),
+                        ),
+                    ];
+                    for (compared, missing_default) in missing_defaults {
+                        if compared != trait_bound
+                            && missing_default
+                            && self.compares_any_field_with(compared)
+                        {
+                            predicates.push(quote::quote!(#parent: #compared));
+                        }
+                    }
+                }
+                for field_with_args in self.get_all_fields() {
+                    predicates.extend(self.get_field_bounds(field_with_args, trait_bound));
+                }
+            }
+        }
+
+        // Remove duplicates while retaining the order
+        let mut seen = std::collections::HashSet::new();
+        predicates.retain(|predicate| seen.insert(predicate.to_string()));
+
+        if predicates.is_empty() {
+            quote::quote!()
+        } else {
+            quote::quote!(where #(#predicates),*)
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::into_iter::*;
+use crate::periodic::*;
+use crate::special_values::*;
+use crate::AbsDiffEqParser;
+
+/// Compares the references `a` and `b` with the method of the given trait.
+///
+/// If the type of the compared values is known, the method is called by its fully qualified path
+/// together with the generic arguments of the trait.
+pub fn get_trait_comparison(
+    approx_trait: ApproxTrait,
+    qualified: Option<(&proc_macro2::TokenStream, &proc_macro2::TokenStream)>,
+    a: proc_macro2::TokenStream,
+    b: proc_macro2::TokenStream,
+    epsilon: &proc_macro2::TokenStream,
+    max_relative: &proc_macro2::TokenStream,
+    max_ulps: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let path = match qualified {
+        Some((base_type, field_rhs)) => quote::quote!(<#base_type as #approx_trait #field_rhs>),
+        None => quote::quote!(#approx_trait),
+    };
+    match approx_trait {
+        ApproxTrait::Abs => quote::quote!(#path::abs_diff_eq(#a, #b, #epsilon)),
+        ApproxTrait::Relative => {
+            quote::quote!(#path::relative_eq(#a, #b, #epsilon, #max_relative))
+        }
+        ApproxTrait::Ulps => quote::quote!(#path::ulps_eq(#a, #b, #epsilon, #max_ulps)),
+    }
+}
+
+/// The `max_relative` value if the given trait makes use of it
+pub fn get_relative_tolerance(
+    approx_trait: ApproxTrait,
+    max_relative: &proc_macro2::TokenStream,
+) -> Option<&proc_macro2::TokenStream> {
+    match approx_trait {
+        ApproxTrait::Relative => Some(max_relative),
+        _ => None,
+    }
+}
+
+impl AbsDiffEqParser {
+    /// Determines if any field is compared by `#[approx(compare = ...)]` with the given trait
+    pub fn compares_any_field_with(&self, approx_trait: ApproxTrait) -> bool {
+        self.get_all_fields()
+            .into_iter()
+            .any(|field_with_args| field_with_args.args.compare == Some(approx_trait))
+    }
+
+    /// Binds the tolerances which are no parameters of the method of the implemented trait.
+    ///
+    /// Fields which are compared with a different method by `#[approx(compare = ...)]` use the
+    /// default values of the object instead.
+    pub fn get_missing_tolerances(&self, approx_trait: ApproxTrait) -> proc_macro2::TokenStream {
+        let max_relative = match approx_trait {
+            ApproxTrait::Relative => None,
+            _ if self.compares_any_field_with(ApproxTrait::Relative) => {
+                let max_relative = self.get_max_relative_default_value();
+                Some(quote::quote!(
+                    #[allow(unused_variables)]
+                    let max_relative = #max_relative;
+                ))
+            }
+            _ => None,
+        };
+        let max_ulps = match approx_trait {
+            ApproxTrait::Ulps => None,
+            _ if self.compares_any_field_with(ApproxTrait::Ulps) => {
+                let max_ulps = self.get_max_ulps_default_value();
+                Some(quote::quote!(
+                    #[allow(unused_variables)]
+                    let max_ulps = #max_ulps;
+                ))
+            }
+            _ => None,
+        };
+        quote::quote!(#max_relative #max_ulps)
+    }
+
+    /// Compares a single field with the method of the derived trait or the one selected by
+    /// `#[approx(compare = ...)]`.
+    ///
+    /// Returns `None` if the field is skipped.
+    pub fn get_single_field_check(
+        &self,
+        approx_trait: ApproxTrait,
+        n: usize,
+        field_with_args: &FieldWithArgs,
+        idents: Option<(syn::Ident, syn::Ident)>,
+    ) -> Option<proc_macro2::TokenStream> {
+        let field_rhs = self.get_field_trait_rhs();
+        // Custom modules provide the method of the derived trait
+        let derived_trait = approx_trait;
+        let approx_trait = field_with_args.args.compare.unwrap_or(approx_trait);
+        if let Some(FieldFormatted {
+            base_type,
+            own_field,
+            other_field,
+            epsilon,
+            max_relative,
+            max_ulps,
+            set_equal,
+            mapping,
+            use_iterator,
+            use_map_keys,
+            compare_with,
+            skip_condition,
+            fallible_cast,
+        }) = self.format_nth_field(n, field_with_args, idents)
+        {
+            let check = if set_equal {
+                Some(quote::quote!(#own_field == #other_field))
+            } else if let Some(compare_with) = compare_with {
+                Some(match compare_with {
+                    CustomComparison::Module(module) => match derived_trait {
+                        ApproxTrait::Abs => quote::quote!(
+                            #module::abs_diff_eq(#own_field, #other_field, #epsilon)
+                        ),
+                        ApproxTrait::Relative => quote::quote!(
+                            #module::relative_eq(#own_field, #other_field, #epsilon, #max_relative)
+                        ),
+                        ApproxTrait::Ulps => quote::quote!(
+                            #module::ulps_eq(#own_field, #other_field, #epsilon, #max_ulps)
+                        ),
+                    },
+                    CustomComparison::Function(function) => {
+                        quote::quote!((#function)(#own_field, #other_field, #epsilon))
+                    }
+                })
+            } else if let Some(map) = mapping {
+                let map_check = get_element_check(
+                    approx_trait,
+                    &field_with_args.args,
+                    quote::quote!(&a),
+                    quote::quote!(&b),
+                    &epsilon,
+                    &max_relative,
+                    &max_ulps,
+                );
+                Some(quote::quote!(
+                    (if let ((Some(a), Some(b))) = (
+                        (#map)(#own_field),
+                        (#map)(#other_field)
+                    ) {
+                        #map_check
+                    } else {
+                        false
+                    })
+                ))
+            } else if use_map_keys {
+                let element_check = get_element_check(
+                    approx_trait,
+                    &field_with_args.args,
+                    quote::quote!(a),
+                    quote::quote!(b),
+                    &epsilon,
+                    &max_relative,
+                    &max_ulps,
+                );
+                Some(quote::quote!(({
+                    let (map1, map2) = (#own_field, #other_field);
+                    map1.len() == map2.len()
+                        && map1.iter().all(|(key, a)| match map2.get(key) {
+                            Some(b) => #element_check,
+                            None => false,
+                        })
+                })))
+            } else if let (true, Some(norm)) = (use_iterator, field_with_args.args.norm) {
+                Some(get_norm_comparison(
+                    &own_field,
+                    &other_field,
+                    &field_with_args.args,
+                    norm,
+                    &epsilon,
+                    get_relative_tolerance(approx_trait, &max_relative),
+                ))
+            } else if use_iterator {
+                let element_check = get_element_check(
+                    approx_trait,
+                    &field_with_args.args,
+                    quote::quote!(a),
+                    quote::quote!(b),
+                    &epsilon,
+                    &max_relative,
+                    &max_ulps,
+                );
+                Some(get_iter_comparison(
+                    &own_field,
+                    &other_field,
+                    &field_with_args.args,
+                    element_check,
+                ))
+            } else if let Some(period) = &field_with_args.args.periodic {
+                Some(with_special_values(
+                    &field_with_args.args,
+                    own_field.clone(),
+                    other_field.clone(),
+                    get_periodic_comparison(
+                        own_field.clone(),
+                        other_field.clone(),
+                        period,
+                        &epsilon,
+                        get_relative_tolerance(approx_trait, &max_relative),
+                    ),
+                ))
+            } else {
+                Some(with_special_values(
+                    &field_with_args.args,
+                    own_field.clone(),
+                    other_field.clone(),
+                    get_trait_comparison(
+                        approx_trait,
+                        Some((&base_type, &field_rhs)),
+                        own_field.clone(),
+                        other_field.clone(),
+                        &epsilon,
+                        &max_relative,
+                        &max_ulps,
+                    ),
+                ))
+            }?;
+            Some(with_skip_condition(
+                skip_condition,
+                with_fallible_cast(fallible_cast.is_some(), check),
+            ))
+        } else {
+            None
+        }
+    }
+}
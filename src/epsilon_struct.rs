@@ -0,0 +1,227 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::AbsDiffEqParser;
+
+impl AbsDiffEqParser {
+    pub fn uses_epsilon_struct(&self) -> bool {
+        self.struct_args.epsilon_struct.unwrap_or(false)
+    }
+
+    /// Name of the generated companion struct which is used as the `Epsilon` type
+    pub fn get_epsilon_struct_ident(&self) -> syn::Ident {
+        let ident = self.base_type.ident();
+        quote::format_ident!("{}Epsilon", ident)
+    }
+
+    /// Fields which are skipped or tested for equality do not need a tolerance
+    fn needs_tolerance(field_with_args: &FieldWithArgs) -> bool {
+        !field_with_args.args.skip.unwrap_or(false)
+            && !field_with_args.args.set_equal.unwrap_or(false)
+    }
+
+    /// Determines how the tolerance of the `n`th field is accessed in the companion struct.
+    pub fn get_epsilon_struct_member(
+        &self,
+        n: usize,
+        field_with_args: &FieldWithArgs,
+    ) -> Option<syn::Member> {
+        if !self.uses_epsilon_struct() || !Self::needs_tolerance(field_with_args) {
+            return None;
+        }
+        match (&field_with_args.ident, &self.base_type) {
+            (Some(ident), _) => Some(syn::Member::Named(ident.clone())),
+            #[allow(unused)]
+            (
+                None,
+                BaseType::Struct {
+                    item_struct,
+                    fields_with_args,
+                },
+            ) => {
+                // Tuple structs only contain the tolerances of fields which are actually compared
+                let index = fields_with_args
+                    .iter()
+                    .take(n)
+                    .filter(|f| Self::needs_tolerance(f))
+                    .count();
+                Some(syn::Member::Unnamed(syn::Index::from(index)))
+            }
+            (None, BaseType::Enum { .. }) => None,
+        }
+    }
+
+    /// Type of the tolerance of a single field.
+    ///
+    /// Fields which are compared directly use the `Epsilon` type of their own (possibly casted)
+    /// type.
+    /// Whenever the compared values can not be determined from the field type alone
+    /// (mappings, iterators, maps and custom comparisons), we fall back to the inferred epsilon type of the struct.
+    fn get_epsilon_struct_field_type(
+        &self,
+        field_with_args: &FieldWithArgs,
+    ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
+        let parent = self.get_epsilon_parent_type();
+        let args = &field_with_args.args;
+        let compared_type = match &args.cast_strategy {
+            Some(TypeCast::CastField(options)) => match &options.to {
+                Some(to) => quote::quote!(#to),
+                None => parent,
+            },
+            _ if args.mapping.is_some()
+                || args.use_iterator.unwrap_or(false)
+                || args.use_map_keys.unwrap_or(false)
+                || args.with_module.is_some()
+                || args.compare_with.is_some()
+                || args.epsilon_mapping.is_some() =>
+            {
+                parent
+            }
+            _ => {
+                let field_type = &field_with_args.ty;
+                quote::quote!(#field_type)
+            }
+        };
+        (
+            quote::quote!(<#compared_type as #ApproxName::AbsDiffEq>::Epsilon),
+            compared_type,
+        )
+    }
+
+    fn get_epsilon_struct_fields(&self) -> Vec<(&FieldWithArgs, syn::Member)> {
+        match &self.base_type {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => fields_with_args
+                .iter()
+                .enumerate()
+                .filter_map(|(n, field_with_args)| {
+                    self.get_epsilon_struct_member(n, field_with_args)
+                        .map(|member| (field_with_args, member))
+                })
+                .collect(),
+            BaseType::Enum { .. } => Vec::new(),
+        }
+    }
+
+    /// Constructs the companion struct where every tolerance takes its default value.
+    ///
+    /// Priority is given to the field attribute, then the object attribute and lastly the default
+    /// value of the compared type.
+    /// The object attribute is a scalar and thus not applied to tolerances which are nested
+    /// companion structs themselves.
+    /// These are recognized by path resolution: the inherent function of the companion struct
+    /// takes precedence over the fallback trait and returns the default value of the nested type.
+    pub fn get_epsilon_struct_default_value(&self, max_relative: bool) -> proc_macro2::TokenStream {
+        let epsilon_struct = self.get_epsilon_struct_ident();
+        let object_value = match max_relative {
+            false => self.struct_args.default_epsilon_value.as_ref(),
+            true => self.struct_args.default_max_relative_value.as_ref(),
+        };
+        let values = self
+            .get_epsilon_struct_fields()
+            .into_iter()
+            .map(|(field_with_args, member)| {
+                let (epsilon_type, compared_type) =
+                    self.get_epsilon_struct_field_type(field_with_args);
+                let (field_value, type_default) = match max_relative {
+                    false => (
+                        field_with_args.args.epsilon_static_value.as_ref(),
+                        quote::quote!(<#compared_type as #ApproxName::AbsDiffEq>::default_epsilon()),
+                    ),
+                    true => (
+                        field_with_args.args.max_relative_static_value.as_ref(),
+                        quote::quote!(
+                            <#compared_type as #ApproxName::RelativeEq>::default_max_relative()
+                        ),
+                    ),
+                };
+                let value = match (field_value, object_value) {
+                    (Some(value), _) => quote::quote!(#value),
+                    (None, Some(value)) => quote::quote!(
+                        <#epsilon_type>::__approx_object_default(#value, #type_default)
+                    ),
+                    (None, None) => type_default,
+                };
+                quote::quote!(#member: #value)
+            });
+        let fallback = object_value.map(|_| {
+            quote::quote!(
+                trait __ApproxObjectDefault: Sized {
+                    fn __approx_object_default(value: Self, _default: Self) -> Self {
+                        value
+                    }
+                }
+                impl<T> __ApproxObjectDefault for T {}
+            )
+        });
+        quote::quote!({
+            #fallback
+            #epsilon_struct { #(#values),* }
+        })
+    }
+
+    /// Generates the companion struct together with its [Default] implementation.
+    pub fn implement_epsilon_struct(&self) -> proc_macro2::TokenStream {
+        let item_struct = match &self.base_type {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => item_struct,
+            // This is checked while parsing
+            BaseType::Enum { .. } => unreachable!(),
+        };
+
+        let vis = &item_struct.vis;
+        let epsilon_struct = self.get_epsilon_struct_ident();
+        let doc = format!(
+            "Tolerances for every compared field of [{}] generated by `#[approx(epsilon_struct)]`",
+            self.base_type.ident()
+        );
+        let fields = self.get_epsilon_struct_fields();
+        let body = match item_struct.fields {
+            syn::Fields::Named(_) => {
+                let named = fields.iter().map(|(field_with_args, member)| {
+                    let (ty, _) = self.get_epsilon_struct_field_type(field_with_args);
+                    let doc = format!("Tolerance of the `{}` field", quote::quote!(#member));
+                    quote::quote!(#[doc = #doc] #vis #member: #ty)
+                });
+                quote::quote!({ #(#named),* })
+            }
+            _ => {
+                let unnamed = fields.iter().map(|(field_with_args, _)| {
+                    let (ty, _) = self.get_epsilon_struct_field_type(field_with_args);
+                    quote::quote!(#[doc = "Tolerance of the corresponding field"] #vis #ty)
+                });
+                quote::quote!(( #(#unnamed),* );)
+            }
+        };
+        let default_value = self.get_epsilon_struct_default_value(false);
+
+        quote::quote!(
+            #[doc = #doc]
+            #[derive(Clone, Debug)]
+            #vis struct #epsilon_struct #body
+
+            #[automatically_derived]
+            impl Default for #epsilon_struct {
+                fn default() -> Self {
+                    #default_value
+                }
+            }
+
+            #[automatically_derived]
+            impl #epsilon_struct {
+                /// Keeps the default tolerances of this struct when it is nested in another
+                /// companion struct whose object attributes only provide scalar values.
+                #[doc(hidden)]
+                #[allow(dead_code)]
+                pub fn __approx_object_default<T>(_value: T, default: Self) -> Self {
+                    default
+                }
+            }
+        )
+    }
+}
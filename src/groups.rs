@@ -0,0 +1,138 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::AbsDiffEqParser;
+
+impl AbsDiffEqParser {
+    /// All groups specified by `#[approx(group = "...")]` in the order of their first occurrence
+    fn get_groups(&self) -> Vec<&syn::Ident> {
+        let mut groups: Vec<&syn::Ident> = Vec::new();
+        for field_with_args in self.get_all_fields() {
+            for group in field_with_args.args.groups.iter() {
+                if !groups.contains(&group) {
+                    groups.push(group);
+                }
+            }
+        }
+        groups
+    }
+
+    /// Compares the fields returned by `select` which may also adapt their arguments.
+    ///
+    /// Enum variants need to match even if none of their fields is selected.
+    pub fn get_selected_comparison(
+        &self,
+        approx_trait: ApproxTrait,
+        select: impl Fn(&FieldWithArgs) -> Option<FieldWithArgs>,
+    ) -> proc_macro2::TokenStream {
+        match &self.base_type {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => {
+                let fields = fields_with_args
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(n, field_with_args)| Some((n, select(field_with_args)?)))
+                    .filter_map(|(n, field_with_args)| {
+                        self.get_single_field_check(approx_trait, n, &field_with_args, None)
+                    });
+                quote::quote!(#(#fields &&)* true)
+            }
+            #[allow(unused)]
+            BaseType::Enum {
+                item_enum,
+                variants_with_args,
+            } => {
+                let variants = variants_with_args.iter().map(|variant_with_args| {
+                    let (pattern1, pattern2, idents) = variant_with_args.get_match_patterns();
+                    let comps = variant_with_args
+                        .fields_with_args
+                        .iter()
+                        .zip(idents)
+                        .filter_map(|(field_with_args, idents)| {
+                            Some((select(field_with_args)?, idents))
+                        })
+                        .filter_map(|(field_with_args, idents)| {
+                            self.get_single_field_check(
+                                approx_trait,
+                                0,
+                                &field_with_args,
+                                Some(idents),
+                            )
+                        });
+                    quote::quote!(
+                        #[allow(unused_variables)]
+                        (#pattern1, #pattern2) => #(#comps &&)* true,
+                    )
+                });
+                quote::quote!(
+                    match (self, other) {
+                        #(#variants)*
+                        _ => false,
+                    }
+                )
+            }
+        }
+    }
+
+    /// Generates inherent methods such as `abs_diff_eq_kinematics` which only compare the fields
+    /// of a single group.
+    pub fn implement_groups(&self, approx_trait: ApproxTrait) -> proc_macro2::TokenStream {
+        let groups = self.get_groups();
+        if groups.is_empty() {
+            return quote::quote!();
+        }
+        let obj_name = &self.base_type.ident();
+        let (impl_generics, ty_generics, _) = self.base_type.generics().split_for_impl();
+        let where_clause = self.generate_where_clause(approx_trait);
+        let rhs_type = self.get_rhs_type();
+        let trait_rhs = self.get_trait_rhs();
+        let special_values = self.get_special_values_helpers_if_used();
+        let missing_tolerances = self.get_missing_tolerances(approx_trait);
+
+        let epsilon_type = quote::quote!(<Self as #ApproxName::AbsDiffEq #trait_rhs>::Epsilon);
+        let (method, tolerances) = match approx_trait {
+            ApproxTrait::Abs => ("abs_diff_eq", quote::quote!(epsilon: #epsilon_type)),
+            ApproxTrait::Relative => (
+                "relative_eq",
+                quote::quote!(epsilon: #epsilon_type, max_relative: #epsilon_type),
+            ),
+            ApproxTrait::Ulps => (
+                "ulps_eq",
+                quote::quote!(epsilon: #epsilon_type, max_ulps: u32),
+            ),
+        };
+        let methods =
+            groups.into_iter().map(|group| {
+                let method_name = syn::Ident::new(&format!("{method}_{group}"), group.span());
+                let doc = format!("Compares the fields of the group `{group}` like `{method}`");
+                let comparison = self.get_selected_comparison(approx_trait, |field_with_args| {
+                    match field_with_args.args.groups.contains(group) {
+                        true => Some(field_with_args.clone()),
+                        false => None,
+                    }
+                });
+                quote::quote!(
+                    #[doc = #doc]
+                    pub fn #method_name(&self, other: &#rhs_type, #tolerances) -> bool {
+                        #missing_tolerances
+                        #comparison
+                    }
+                )
+            });
+
+        quote::quote!(
+            const _: () = {
+                #special_values
+
+                #[automatically_derived]
+                impl #impl_generics #obj_name #ty_generics
+                #where_clause
+                {
+                    #(#methods)*
+                }
+            };
+        )
+    }
+}
@@ -0,0 +1,283 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::compare::*;
+use crate::periodic::*;
+use crate::special_values::*;
+
+/// Compares the references `a` and `b` to two values with the method of the given trait.
+///
+/// This is used for the elements of iterators and maps where the type of the elements is not
+/// known.
+pub fn get_element_check(
+    approx_trait: ApproxTrait,
+    args: &FieldArgs,
+    a: proc_macro2::TokenStream,
+    b: proc_macro2::TokenStream,
+    epsilon: &proc_macro2::TokenStream,
+    max_relative: &proc_macro2::TokenStream,
+    max_ulps: &proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let check = match &args.periodic {
+        Some(period) => get_periodic_comparison(
+            a.clone(),
+            b.clone(),
+            period,
+            epsilon,
+            get_relative_tolerance(approx_trait, max_relative),
+        ),
+        None => get_trait_comparison(
+            approx_trait,
+            None,
+            a.clone(),
+            b.clone(),
+            epsilon,
+            max_relative,
+            max_ulps,
+        ),
+    };
+    with_special_values(args, a, b, check)
+}
+
+/// Defines `__approx_matching` which computes a maximum bipartite matching.
+///
+/// The entry `adjacency[i][j]` states if the `i`th element of the first collection is
+/// approximately equal to the `j`th element of the second one.
+/// The result contains the matched element of the first collection for every element of the
+/// second one.
+/// Augmenting paths (Kuhn's algorithm) ensure that overlapping tolerances do not lead to false
+/// negatives as they could with a greedy assignment.
+/// The `alloc` crate is imported as well such that collecting the elements also works in
+/// `#![no_std]` crates.
+pub fn get_matching_helper() -> proc_macro2::TokenStream {
+    quote::quote!(
+        extern crate alloc;
+        fn __approx_matching(
+            adjacency: &[alloc::vec::Vec<bool>],
+        ) -> alloc::vec::Vec<Option<usize>> {
+            fn augment(
+                i: usize,
+                adjacency: &[alloc::vec::Vec<bool>],
+                visited: &mut [bool],
+                matched: &mut [Option<usize>],
+            ) -> bool {
+                for (j, edge) in adjacency[i].iter().enumerate() {
+                    if *edge && !visited[j] {
+                        visited[j] = true;
+                        let free = match matched[j] {
+                            None => true,
+                            Some(k) => augment(k, adjacency, visited, matched),
+                        };
+                        if free {
+                            matched[j] = Some(i);
+                            return true;
+                        }
+                    }
+                }
+                false
+            }
+            let n = adjacency.first().map(|row| row.len()).unwrap_or(0);
+            let mut matched = alloc::vec![None; n];
+            for i in 0..adjacency.len() {
+                let mut visited = alloc::vec![false; n];
+                augment(i, adjacency, &mut visited, &mut matched);
+            }
+            matched
+        }
+    )
+}
+
+/// Adapters which restrict an iterator to the window given by `skip`, `stride` and `take`.
+///
+/// The elements are skipped first, then every `stride`th element is taken until `take` elements
+/// were collected.
+pub fn get_iter_window(options: Option<&IterOptions>) -> proc_macro2::TokenStream {
+    let options = match options {
+        Some(options) => options,
+        None => return quote::quote!(),
+    };
+    let skip = options.skip.as_ref().map(|x| quote::quote!(.skip(#x)));
+    let stride = options.stride.as_ref().map(|x| quote::quote!(.step_by(#x)));
+    let take = options.take.as_ref().map(|x| quote::quote!(.take(#x)));
+    quote::quote!(#skip #stride #take)
+}
+
+/// Position of the `index`th compared element in the original iterator
+pub fn get_iter_index(
+    options: Option<&IterOptions>,
+    index: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let skip = options.and_then(|x| x.skip.as_ref());
+    let stride = options.and_then(|x| x.stride.as_ref());
+    match (skip, stride) {
+        (None, None) => index,
+        (Some(skip), None) => quote::quote!((#skip) + #index),
+        (None, Some(stride)) => quote::quote!(#index * (#stride)),
+        (Some(skip), Some(stride)) => quote::quote!((#skip) + #index * (#stride)),
+    }
+}
+
+/// Computes the norms of the elementwise difference and of both iterators.
+///
+/// The expression evaluates to `None` if the lengths of the iterators do not match and otherwise
+/// to `Some((diff, norm1, norm2))` where `None` represents a norm of zero for empty iterators.
+/// The accumulators start with the first element such that the element type does not need to be
+/// named.
+pub fn get_norms(
+    own_field: &proc_macro2::TokenStream,
+    other_field: &proc_macro2::TokenStream,
+    args: &FieldArgs,
+    norm: Norm,
+) -> proc_macro2::TokenStream {
+    let options = args.iter_options.as_ref();
+    let window = get_iter_window(options);
+    let shortest = options.is_some_and(|x| x.is_shortest());
+    let (accumulate, finish) = match norm {
+        Norm::L1 => (
+            quote::quote!(|acc: Option<_>, x| Some(match acc {
+                Some(acc) => acc + x,
+                None => x,
+            })),
+            quote::quote!(|x| x),
+        ),
+        Norm::L2 => (
+            quote::quote!(|acc: Option<_>, x| Some(match acc {
+                Some(acc) => acc + x * x,
+                None => x * x,
+            })),
+            quote::quote!(|x| x.sqrt()),
+        ),
+        Norm::LInf => (
+            quote::quote!(|acc: Option<_>, x| Some(match acc {
+                Some(acc) if acc >= x => acc,
+                _ => x,
+            })),
+            quote::quote!(|x| x),
+        ),
+    };
+    quote::quote!({
+        let mut iter1 = core::iter::IntoIterator::into_iter(#own_field)#window;
+        let mut iter2 = core::iter::IntoIterator::into_iter(#other_field)#window;
+        let accumulate = #accumulate;
+        let (mut diff, mut norm1, mut norm2) = (None, None, None);
+        let mut same_length = true;
+        loop {
+            match (iter1.next(), iter2.next()) {
+                (Some(a), Some(b)) => {
+                    let (a, b) = (*a, *b);
+                    diff = accumulate(diff, (a - b).abs());
+                    norm1 = accumulate(norm1, a.abs());
+                    norm2 = accumulate(norm2, b.abs());
+                }
+                (None, None) => break,
+                _ => {
+                    same_length = #shortest;
+                    break;
+                }
+            }
+        }
+        match same_length {
+            true => Some((diff.map(#finish), norm1.map(#finish), norm2.map(#finish))),
+            false => None,
+        }
+    })
+}
+
+/// Compares two iterators by the norm of their elementwise difference.
+///
+/// Similarly to the relative comparison of single values, the difference is compared against
+/// `max_relative` times the larger norm of both iterators if it exceeds `epsilon`.
+pub fn get_norm_comparison(
+    own_field: &proc_macro2::TokenStream,
+    other_field: &proc_macro2::TokenStream,
+    args: &FieldArgs,
+    norm: Norm,
+    epsilon: &proc_macro2::TokenStream,
+    max_relative: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let norms = get_norms(own_field, other_field, args, norm);
+    let relative = match max_relative {
+        Some(max_relative) => quote::quote!(
+            || match (norm1, norm2) {
+                (Some(norm1), Some(norm2)) if norm1 >= norm2 => diff <= norm1 * #max_relative,
+                (_, Some(largest)) | (Some(largest), None) => diff <= largest * #max_relative,
+                (None, None) => false,
+            }
+        ),
+        None => quote::quote!(),
+    };
+    quote::quote!((match #norms {
+        Some((Some(diff), norm1, norm2)) => diff <= #epsilon #relative,
+        Some((None, _, _)) => true,
+        None => false,
+    }))
+}
+
+/// Compares two fields which are iterated over.
+///
+/// The `element_check` compares the references `a` and `b` to the items of both iterators.
+/// Unordered collections are compared by matching every element of one collection to a distinct
+/// element of the other one which requires `O(n²)` comparisons.
+/// When both iterators know their exact length, differing lengths fail without comparing any
+/// elements.
+pub fn get_iter_comparison(
+    own_field: &proc_macro2::TokenStream,
+    other_field: &proc_macro2::TokenStream,
+    args: &FieldArgs,
+    element_check: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let options = args.iter_options.as_ref();
+    let window = get_iter_window(options);
+    if args.unordered.unwrap_or(false) {
+        let matching_helper = get_matching_helper();
+        quote::quote!(({
+            #matching_helper
+            let items1: alloc::vec::Vec<_> =
+                core::iter::IntoIterator::into_iter(#own_field)#window.collect();
+            let items2: alloc::vec::Vec<_> =
+                core::iter::IntoIterator::into_iter(#other_field)#window.collect();
+            items1.len() == items2.len() && {
+                let adjacency: alloc::vec::Vec<alloc::vec::Vec<bool>> = items1
+                    .iter()
+                    .map(|a| items2.iter().map(|b| #element_check).collect())
+                    .collect();
+                __approx_matching(&adjacency).iter().all(|m| m.is_some())
+            }
+        }))
+    } else if options.is_some_and(|x| x.is_shortest()) {
+        quote::quote!(({
+            let iter1 = core::iter::IntoIterator::into_iter(#own_field)#window;
+            let iter2 = core::iter::IntoIterator::into_iter(#other_field)#window;
+            iter1.zip(iter2).all(|(a, b)| {
+                let (a, b) = (&a, &b);
+                #element_check
+            })
+        }))
+    } else {
+        quote::quote!(({
+            let mut iter1 = core::iter::IntoIterator::into_iter(#own_field)#window;
+            let mut iter2 = core::iter::IntoIterator::into_iter(#other_field)#window;
+            let mut res = true;
+            // Iterators such as ExactSizeIterator report their exact length upfront
+            if let ((low1, Some(high1)), (low2, Some(high2))) =
+                (iter1.size_hint(), iter2.size_hint())
+            {
+                if low1 == high1 && low2 == high2 && low1 != low2 {
+                    res = false;
+                }
+            }
+            while res {
+                match (iter1.next(), iter2.next()) {
+                    (None, None) => break,
+                    (Some(a), Some(b)) => {
+                        let (a, b) = (&a, &b);
+                        if !#element_check {
+                            res = false;
+                        }
+                    },
+                    _ => res = false,
+                }
+            }
+            res
+        }))
+    }
+}
@@ -1,32 +1,67 @@
 #![deny(missing_docs)]
 //! This crate provides derive macros for the
-//! [AbsDiffEq](https://docs.rs/approx/latest/approx/trait.AbsDiffEq.html) and
-//! [RelativeEq](https://docs.rs/approx/latest/approx/trait.RelativeEq.html) traits of the
+//! [AbsDiffEq](https://docs.rs/approx/latest/approx/trait.AbsDiffEq.html),
+//! [RelativeEq](https://docs.rs/approx/latest/approx/trait.RelativeEq.html) and
+//! [UlpsEq](https://docs.rs/approx/latest/approx/trait.UlpsEq.html) traits of the
 //! [approx](https://docs.rs/approx/latest/approx/) crate.
 //!
-//! These derive macros only implement both traits with `...<Rhs = Self>`.
+//! By default, these derive macros implement the traits with `...<Rhs = Self>`.
+//! Structs can be compared against a different type by specifying
+//! [`#[approx(rhs = ...)]`](#rhs).
 //! The macros infer the `EPSILON` type of the [AbsDiffEq] trait by looking
 //! at the type of the first struct or enum field or any type specified by the user.
 //!
 //! This table lists all attributes which can be used to customize the derived traits.
-//! They are ordered in descending priority, meaning setting the `#[approx(equal)]` will overwrite
-//! any specifications made in the `#[approx(map = ...)]` attribute.
+//! Multiple arguments can be combined in a single attribute by separating them with commas, such
+//! as `#[approx(cast_field, static_epsilon = 0.1)]`.
 //!
 //! | Field Attribute | Functionality |
 //! |:--- | --- |
 //! | [`#[approx(skip)]`](#skipping-fields) | Skips the field entirely |
+//! | [`#[approx(skip_if = ..)]`](#conditional-comparisons) | Skips the field if the condition holds |
+//! | [`#[approx(compare_if = ..)]`](#conditional-comparisons) | Only compares the field if the condition holds |
 //! | [`#[approx(equal)]`](#testing-for-equality) | Checks this field with `==` for Equality |
+//! | [`#[approx(with = ..)]`](#custom-comparisons) | Compares the field with the functions of a module. |
+//! | [`#[approx(compare_with = ..)]`](#custom-comparisons) | Compares the field with a single function. |
 //! | [`#[approx(cast_field)]`](#casting-fields) | Casts the field with `.. as ..` syntax. |
 //! | [`#[approx(cast_value)]`](#casting-fields) | Casts the epsilon value with `.. as ..` syntax. |
+//! | [`#[approx(cast_field = into)]`](#conversions) | Converts the field by `Into` or `TryInto`. |
+//! | [`#[approx(cast_field(to = ..))]`](#conversions) | Casts the field or epsilon value to an explicit type. |
+//! | [`#[approx(periodic = ..)]`](#periodic-values) | Compares values in a periodic domain. |
+//! | [`#[approx(nan_equal)]`](#special-values) | Considers two `NaN` values to be equal. |
+//! | [`#[approx(inf_equal)]`](#special-values) | Considers two infinite values with the same sign to be equal. |
 //! | [`#[approx(map = ..)]`](#mapping-values) | Maps values before comparing them. |
 //! | [`#[approx(epsilon_map = ..)]`](#mapping-epsilon-values) | Maps epsilon values before using them. |
 //! | [`#[approx(static_epsilon = ..)]`](#static-values) | Defines a static epsilon value for this particular field. |
+//! | [`#[approx(epsilon = ..)]`](#tolerance-expressions) | Computes the epsilon value from both compared objects. |
+//! | [`#[approx(max_relative = ..)]`](#tolerance-expressions) | Computes the `max_relative` value from both compared objects. |
+//! | [`#[approx(static_max_ulps = ..)]`](#ulpseq) | Defines a static `max_ulps` value for this particular field. |
+//! | [`#[approx(max_ulps_map = ..)]`](#ulpseq) | Maps `max_ulps` values before using them. |
+//! | [`#[approx(compare = ..)]`](#comparison-method) | Compares the field with `abs`, `relative` or `ulps` in every implementation. |
 //! | [`#[approx(into_iter)]`](#into-iterator) | Tries to use the `into_iterator` method to compare fields. |
+//! | [`#[approx(into_iter(...))]`](#iterator-options) | Restricts the compared elements of iterators. |
+//! | [`#[approx(into_iter, norm = ..)]`](#norms) | Compares iterators by the norm of their difference. |
+//! | [`#[approx(into_iter, unordered)]`](#unordered-collections) | Compares iterators regardless of the order of their elements. |
+//! | [`#[approx(map_keys)]`](#maps) | Compares the values of two maps by key. |
+//! | [`#[approx(bound = "...")]`](#bounds) | Replaces the inferred bounds of this field. |
+//! | [`#[approx(group = "...")]`](#field-groups) | Generates methods which only compare the fields of this group. |
+//! | [`#[approx(profile(name(...)))]`](#profiles) | Overrides the static tolerances of this field for a profile. |
 //! | | |
 //! | **Object Attribute** | |
 //! | [`#[approx(default_epsilon = ...)]`](#default-epsilon) | Sets the default epsilon value |
 //! | [`#[approx(default_max_relative = ...)]`](#default-max-relative) | Sets the default `max_relative` value. |
+//! | [`#[approx(default_max_ulps = ...)]`](#ulpseq) | Sets the default `max_ulps` value. |
 //! | [`#[approx(epsilon_type = ...)]`](#epsilon-type) | Sets the type of the epsilon value |
+//! | [`#[approx(rhs = ...)]`](#rhs) | Compares against a different type |
+//! | [`#[approx(epsilon_struct)]`](#epsilon-struct) | Generates a struct with one tolerance per field |
+//! | [`#[approx(profile(name(...)))]`](#profiles) | Defines a named set of default tolerances |
+//! | [`#[approx(also(name = ...))]`](#computed-properties) | Additionally compares a computed property |
+//! | [`#[approx(mismatches)]`](#mismatch-report) | Generates a report of all mismatching fields |
+//! | [`#[approx(remote = "...")]`](#remote-types) | Generates comparison functions for a type of another crate |
+//! | [`#[approx(transparent)]`](#transparent) | Forwards the traits to the single compared field |
+//! | [`#[approx(via = ..., convert = ...)]`](#conversion) | Compares both values after converting them into another type |
+//! | [`#[approx(nan_equal, inf_equal)]`](#special-values) | Applies `nan_equal` and `inf_equal` to all fields |
+//! | [`#[approx(bound = "...")]`](#bounds) | Replaces all inferred bounds |
 //!
 //! # Usage
 //!
@@ -147,6 +182,59 @@
 //! assert_abs_diff_eq!(player1, player2, epsilon = 0.5);
 //! ```
 //!
+//! ## Conditional Comparisons
+//! Some fields are only meaningful in certain states.
+//! The field attribute `#[approx(skip_if = ...)]` skips a field whenever the condition is true
+//! while `#[approx(compare_if = ...)]` only compares it when the condition is true.
+//! Both conditions can refer to `self` and `other` and work for structs and enums alike.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Body {
+//!     position: f32,
+//!     #[approx(equal)]
+//!     is_moving: bool,
+//!     #[approx(compare_if = self.is_moving || other.is_moving)]
+//!     velocity: f32,
+//! }
+//! let b1 = Body { position: 1.0, is_moving: false, velocity: 0.0 };
+//! let b2 = Body { position: 1.0, is_moving: false, velocity: 5.0 };
+//!
+//! assert_abs_diff_eq!(b1, b2);
+//! assert_abs_diff_ne!(
+//!     Body { is_moving: true, ..b1 },
+//!     Body { is_moving: true, ..b2 }
+//! );
+//! ```
+//!
+//! ## Field Groups
+//! Large objects often consist of several groups of related fields.
+//! Every group specified by `#[approx(group = "name")]` generates the inherent methods
+//! `abs_diff_eq_name(&self, other, epsilon)` and for the respective derive macros
+//! `relative_eq_name(&self, other, epsilon, max_relative)` and
+//! `ulps_eq_name(&self, other, epsilon, max_ulps)` which only compare the fields of this group.
+//! A field can belong to several groups by repeating the attribute.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! struct State {
+//!     #[approx(group = "kinematics")]
+//!     position: f64,
+//!     #[approx(group = "kinematics", group = "energy")]
+//!     velocity: f64,
+//!     #[approx(group = "chemistry")]
+//!     concentration: f64,
+//! }
+//! let s1 = State { position: 1.0, velocity: 2.0, concentration: 0.1 };
+//! let s2 = State { position: 1.05, velocity: 2.0, concentration: 0.5 };
+//!
+//! assert!(s1.abs_diff_eq_kinematics(&s2, 0.1));
+//! assert!(s1.relative_eq_energy(&s2, 0.0, 0.0));
+//! assert!(!s1.relative_eq_chemistry(&s2, 0.1, 0.1));
+//! ```
+//!
 //! ## Testing for [Equality](core::cmp::Eq)
 //!
 //! When identical equality is desired, we can specify this with the `#[approx(equal)]` attribute.
@@ -162,6 +250,19 @@
 //! }
 //! ```
 //!
+//! Since the field is compared exactly, it can not be [mapped](#mapping-values) or
+//! [casted](#casting-fields) at the same time.
+//! ```compile_fail
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! struct Length {
+//!     // error: `equal` can not be combined with `map`
+//!     #[approx(equal)]
+//!     #[approx(map = |x: &f32| Some(2.0 * x))]
+//!     meters: f32,
+//! }
+//! ```
+//!
 //! Note that in this case, the type of the epsilon value for the implementation of
 //! [AbsDiffEq](https://docs.rs/approx/latest/approx/trait.AbsDiffEq.html) is inferred from the
 //! first field of the `Prediction` struct.
@@ -265,6 +366,51 @@
 //! ));
 //! ```
 //!
+//! ### Conversions
+//! Casting with `as` only works for primitive numeric types.
+//! Other types can be converted by `#[approx(cast_field = into)]` which uses [Into] or
+//! `#[approx(cast_field = try_into)]` which uses [TryInto].
+//! If the conversion by `try_into` fails, the comparison fails as well.
+//! The target type is inferred as before but can be given explicitly by
+//! `#[approx(cast_field(to = Type))]`.
+//! Both options can be combined by `#[approx(cast_field(to = Type, by = into))]` and are
+//! available for `cast_value` in the same way.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(Clone, PartialEq, Debug)]
+//! struct Half(u16);
+//!
+//! impl From<Half> for f32 {
+//!     fn from(half: Half) -> Self {
+//!         // Simplified conversion which ignores the sign and the exponent
+//!         half.0 as f32 / 1024.0
+//!     }
+//! }
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Pixel {
+//!     brightness: f32,
+//!     #[approx(cast_field = into)]
+//!     alpha: Half,
+//!     #[approx(cast_field(to = f32, by = try_into))]
+//!     index: u8,
+//! }
+//!
+//! let p1 = Pixel {
+//!     brightness: 0.5,
+//!     alpha: Half(512),
+//!     index: 1,
+//! };
+//! let p2 = Pixel {
+//!     brightness: 0.5,
+//!     alpha: Half(520),
+//!     index: 1,
+//! };
+//! assert_abs_diff_ne!(p1, p2);
+//! assert_abs_diff_eq!(p1, p2, epsilon = 0.01);
+//! ```
+//!
 //! ## Mapping Values
 //!
 //! We can map values before comparing them.
@@ -322,6 +468,117 @@
 //!     next_doctors_appointment: Time,
 //! }
 //! ```
+//! To compare different variants of the enum itself, it can be [converted](#conversion) as a
+//! whole.
+//!
+//! ## Custom Comparisons
+//!
+//! Types of other crates which do not implement the approx traits can be compared by
+//! user-provided functions.
+//! Similarly to serde, `#[approx(with = module)]` calls `module::abs_diff_eq(&a, &b, epsilon)`,
+//! `module::relative_eq(&a, &b, epsilon, max_relative)` and
+//! `module::ulps_eq(&a, &b, epsilon, max_ulps)` for the respective traits.
+//! Only the functions of the derived traits need to exist.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! mod timestamp {
+//!     #[derive(Clone, Copy, PartialEq, Debug)]
+//!     pub struct Timestamp(pub u64);
+//!
+//!     pub fn abs_diff_eq(a: &Timestamp, b: &Timestamp, epsilon: f64) -> bool {
+//!         a.0.abs_diff(b.0) as f64 <= epsilon * 1000.0
+//!     }
+//!
+//!     pub fn relative_eq(a: &Timestamp, b: &Timestamp, epsilon: f64, _: f64) -> bool {
+//!         abs_diff_eq(a, b, epsilon)
+//!     }
+//! }
+//! use timestamp::Timestamp;
+//!
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! struct Sample {
+//!     value: f64,
+//!     #[approx(with = timestamp)]
+//!     recorded: Timestamp,
+//! }
+//! # let s1 = Sample { value: 1.0, recorded: Timestamp(10_000) };
+//! # let s2 = Sample { value: 1.0, recorded: Timestamp(10_400) };
+//! # assert_relative_eq!(s1, s2, epsilon = 0.5);
+//! # assert_relative_ne!(s1, s2, epsilon = 0.1);
+//! ```
+//! When a single function suffices, `#[approx(compare_with = function)]` calls
+//! `function(&a, &b, epsilon)` for every derived trait.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! # #[derive(Clone, Copy, PartialEq, Debug)]
+//! # struct Timestamp(u64);
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Sample {
+//!     value: f64,
+//!     #[approx(compare_with = |a: &Timestamp, b: &Timestamp, eps: f64| {
+//!         a.0.abs_diff(b.0) as f64 <= eps * 1000.0
+//!     })]
+//!     recorded: Timestamp,
+//! }
+//! # let s1 = Sample { value: 1.0, recorded: Timestamp(10_000) };
+//! # let s2 = Sample { value: 1.0, recorded: Timestamp(10_400) };
+//! # assert_abs_diff_eq!(s1, s2, epsilon = 0.5);
+//! # assert_abs_diff_ne!(s1, s2, epsilon = 0.1);
+//! ```
+//!
+//! ## Periodic Values
+//! Angles, phases or positions in a periodic box wrap around.
+//! With `#[approx(periodic = ...)]`, the minimal image of the difference is compared against
+//! epsilon such that `359.9°` and `0.1°` only differ by `0.2°`.
+//! The period can be any expression including other fields of `self` and also applies to the
+//! elements of [iterators](#into-iterator).
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Particle {
+//!     #[approx(periodic = 360.0)]
+//!     angle: f64,
+//!     #[approx(equal)]
+//!     box_length: f64,
+//!     #[approx(into_iter, periodic = self.box_length)]
+//!     position: [f64; 2],
+//! }
+//! let p1 = Particle { angle: 359.9, box_length: 10.0, position: [0.001, 5.0] };
+//! let p2 = Particle { angle: 0.1, box_length: 10.0, position: [9.999, 5.0] };
+//!
+//! assert_abs_diff_eq!(p1, p2, epsilon = 0.25);
+//! ```
+//! For [RelativeEq], the difference may alternatively be within `max_relative` times the larger
+//! magnitude of both values.
+//!
+//! ## Special Values
+//! By default, `NaN` is never approximately equal to anything and infinite values are compared
+//! as by the traits of `approx`.
+//! The `#[approx(nan_equal)]` attribute considers two `NaN` values to be equal while
+//! `#[approx(inf_equal)]` does so for two infinite values of the same sign.
+//! Both can be specified on single fields or on the whole object and also apply to mapped values,
+//! the elements of [iterators](#into-iterator) and the values of [maps](#maps).
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(inf_equal)]
+//! struct Sensor {
+//!     #[approx(nan_equal)]
+//!     reading: f64,
+//!     #[approx(into_iter, nan_equal)]
+//!     history: Vec<f64>,
+//!     limit: f64,
+//! }
+//! let s1 = Sensor { reading: f64::NAN, history: vec![1.0, f64::NAN], limit: f64::INFINITY };
+//! let s2 = Sensor { reading: f64::NAN, history: vec![1.0, f64::NAN], limit: f64::INFINITY };
+//!
+//! assert_abs_diff_eq!(s1, s2);
+//! ```
+//! Values which are not `f32` or `f64` are compared as usual.
 //!
 //! ## Mapping Epsilon Values
 //!
@@ -382,6 +639,37 @@
 //! // b field values.
 //! assert_abs_diff_ne!(r1, r2, epsilon = 1e-4);
 //! ```
+//!
+//! ## Tolerance Expressions
+//! Values which carry their own uncertainty need a tolerance which depends on both compared
+//! objects.
+//! The expressions of `#[approx(epsilon = ...)]` and `#[approx(max_relative = ...)]` can refer to
+//! `self`, `other` and the incoming `epsilon` and `max_relative` values.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Measurement {
+//!     #[approx(epsilon = 2.0 * (self.sigma + other.sigma))]
+//!     value: f64,
+//!     sigma: f64,
+//! }
+//! let m1 = Measurement { value: 10.0, sigma: 0.5 };
+//! let m2 = Measurement { value: 11.9, sigma: 0.5 };
+//!
+//! assert_abs_diff_eq!(m1, m2);
+//! assert_abs_diff_ne!(m1, Measurement { value: 12.1, sigma: 0.5 });
+//! ```
+//! The tolerance of a field can not depend on the field itself.
+//! ```compile_fail
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Measurement {
+//!     // error: the tolerance of `value` can not depend on the field itself
+//!     #[approx(epsilon = self.value * 0.1)]
+//!     value: f64,
+//! }
+//! ```
 //! # Object Attributes
 //! ## Default Epsilon
 //! The [AbsDiffEq] trait allows to specify a default value for its `EPSILON` associated type.
@@ -466,6 +754,391 @@
 //! assert_relative_ne!(car1, car2, max_relative = 0.01);
 //! ```
 //!
+//! ## Rhs
+//! Structs can be compared against a different type with the same field names (or the same
+//! number of fields for tuple structs).
+//! The fields are matched by their name (or position) and compared individually.
+//! Since the traits of the [approx](https://docs.rs/approx/latest/approx/) crate require
+//! `PartialEq<Rhs>`, this implementation has to be provided by the user.
+//!
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(Debug)]
+//! struct Reference {
+//!     temperature: f64,
+//!     pressure: f64,
+//! }
+//!
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! #[approx(rhs = Reference)]
+//! struct Measurement {
+//!     temperature: f64,
+//!     pressure: f64,
+//!     #[approx(skip)]
+//!     sensor_id: usize,
+//! }
+//!
+//! impl PartialEq<Reference> for Measurement {
+//!     fn eq(&self, other: &Reference) -> bool {
+//!         self.temperature == other.temperature && self.pressure == other.pressure
+//!     }
+//! }
+//!
+//! let measured = Measurement { temperature: 293.1, pressure: 1.01, sensor_id: 3 };
+//! let reference = Reference { temperature: 293.15, pressure: 1.0 };
+//! assert_relative_eq!(measured, reference, max_relative = 0.01);
+//! ```
+//!
+//! The types of the fields of the other type are not known to the macros.
+//! For generic types, only the type which determines the [epsilon type](#epsilon-type) receives
+//! an inferred bound and every other generic field requires a bound given by
+//! [`#[approx(bound = "...")]`](#bounds).
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(Debug)]
+//! struct Reference<T, U> {
+//!     value: T,
+//!     weight: U,
+//! }
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(rhs = Reference<T, U>)]
+//! struct Measurement<T, U> {
+//!     value: T,
+//!     #[approx(bound = "U: AbsDiffEq<Epsilon = T::Epsilon>")]
+//!     weight: U,
+//! }
+//! # impl<T: PartialEq, U: PartialEq> PartialEq<Reference<T, U>> for Measurement<T, U> {
+//! #     fn eq(&self, other: &Reference<T, U>) -> bool {
+//! #         self.value == other.value && self.weight == other.weight
+//! #     }
+//! # }
+//!
+//! let measured = Measurement { value: 1.0, weight: 2.0 };
+//! let reference = Reference { value: 1.0, weight: 2.1 };
+//! assert_abs_diff_eq!(measured, reference, epsilon = 0.2);
+//! ```
+//!
+//! If the other type lacks one of the compared fields, the compiler will point to it.
+//! ```compile_fail
+//! # use approx_derive::*;
+//! struct Reference {
+//!     temperature: f64,
+//! }
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(rhs = Reference)]
+//! struct Measurement {
+//!     temperature: f64,
+//!     // error[E0609]: no field `pressure` on type `&Reference`
+//!     pressure: f64,
+//! }
+//! # impl PartialEq<Reference> for Measurement {
+//! #     fn eq(&self, other: &Reference) -> bool { true }
+//! # }
+//! ```
+//!
+//! ## Epsilon Struct
+//! By default, all fields share a single `Epsilon` type.
+//! With `#[approx(epsilon_struct)]`, a companion struct named `<Name>Epsilon` is generated which
+//! contains one tolerance for every compared field and is used as the `Epsilon` type.
+//! This allows to specify individual tolerances at runtime, even when mixing fields of different
+//! types.
+//! Fields which derive the traits themselves can be nested.
+//!
+//! The tolerances of the companion struct have the `Epsilon` type of the compared field.
+//! Fields using `map`, `epsilon_map` or `into_iter` fall back to the inferred
+//! [epsilon type](#epsilon-type) of the struct.
+//! Its [Default] implementation (and `default_max_relative` of the [RelativeEq] trait) uses the
+//! `static_epsilon` and `static_max_relative` field attributes, then the `default_epsilon` and
+//! `default_max_relative` object attributes and finally the default values of the field types.
+//! Nested companion structs keep their own default values instead of the object attributes.
+//! This mode is only available for non-generic structs and can not be combined with
+//! `epsilon_type` since the tolerances do not share a single type.
+//!
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(epsilon_struct)]
+//! struct Particle {
+//!     #[approx(static_epsilon = 1e-3)]
+//!     position: f64,
+//!     velocity: f32,
+//!     #[approx(equal)]
+//!     id: usize,
+//! }
+//!
+//! let p1 = Particle { position: 1.0, velocity: 2.0, id: 1 };
+//! let p2 = Particle { position: 1.0005, velocity: 2.1, id: 1 };
+//!
+//! assert_abs_diff_ne!(p1, p2);
+//! assert_abs_diff_eq!(p1, p2, epsilon = ParticleEpsilon {
+//!     position: 1e-3,
+//!     velocity: 0.2,
+//! });
+//! ```
+//!
+//! ## Profiles
+//! Sometimes the same objects need to be compared with different tolerances depending on the
+//! environment, for example strictly on one platform and more loosely on another.
+//! Every `#[approx(profile(name(...)))]` on the object defines a named set of
+//! `default_epsilon`, `default_max_relative` and `default_max_ulps` values.
+//! The profiles become the variants of an enum named `<Name>Profile` and the inherent methods
+//! `abs_diff_eq_profile(&self, other, profile)` and for the respective derive macros
+//! `relative_eq_profile` and `ulps_eq_profile` compare both objects with the tolerances of the
+//! selected profile.
+//! Values which are not given by the profile fall back to the default values of the object.
+//!
+//! Fields can override their tolerances for a profile by
+//! `#[approx(profile(name(static_epsilon = ...)))]` together with `static_max_relative` and
+//! `static_max_ulps`.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! #[approx(
+//!     profile(strict(default_max_relative = 1e-8)),
+//!     profile(loose(default_epsilon = 1e-4, default_max_relative = 1e-3))
+//! )]
+//! struct Simulation {
+//!     energy: f64,
+//!     #[approx(profile(loose(static_max_relative = 1e-1)))]
+//!     temperature: f64,
+//! }
+//!
+//! let s1 = Simulation { energy: 1000.0, temperature: 300.0 };
+//! let s2 = Simulation { energy: 1000.5, temperature: 310.0 };
+//!
+//! assert!(!s1.relative_eq_profile(&s2, SimulationProfile::Strict));
+//! assert!(s1.relative_eq_profile(&s2, SimulationProfile::Loose));
+//! ```
+//! Every profile can only be specified once on the object and on each field.
+//! Profiles can not be combined with `epsilon_struct` since its tolerances are no scalars.
+//!
+//! ## Computed Properties
+//! Often the meaningful comparison concerns quantities which are derived from the fields such as
+//! the total energy or the center of mass of a system.
+//! Every `#[approx(also(name = closure))]` on the object evaluates the closure for both compared
+//! objects and compares the results in addition to the regular fields.
+//! The closure receives a reference to the object.
+//! By default, the results are compared with the tolerances passed to the method which requires
+//! them to have the same epsilon type as the object.
+//! Individual tolerances can be given by `epsilon`, `max_relative` and `max_ulps`.
+//! In the [mismatch report](#mismatch-report), computed properties appear under their name.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(also(energy = |p: &Self| 0.5 * p.mass * p.velocity.powi(2), epsilon = 1e-6))]
+//! #[approx(mismatches)]
+//! struct Particle {
+//!     mass: f64,
+//!     velocity: f64,
+//! }
+//!
+//! let p1 = Particle { mass: 2.0, velocity: 1.0 };
+//! let p2 = Particle { mass: 2.0, velocity: 1.01 };
+//!
+//! assert_abs_diff_ne!(p1, p2, epsilon = 0.1);
+//! let mismatches = p1.approx_mismatches(&p2, 0.1);
+//! assert_eq!(mismatches.len(), 1);
+//! assert_eq!(mismatches[0].path, "energy");
+//! ```
+//!
+//! ## Remote Types
+//! The orphan rule forbids implementing the approx traits for types of other crates.
+//! Similarly to serde, such types can be mirrored by a local definition with
+//! `#[approx(remote = "path::Type")]`.
+//! Instead of the traits, the derive macros generate the associated functions
+//! `abs_diff_eq(&a, &b, epsilon)`, `relative_eq(&a, &b, epsilon, max_relative)` and
+//! `ulps_eq(&a, &b, epsilon, max_ulps)` on the local definition which compare two values of the
+//! remote type with the attributes of the mirrored fields.
+//! They can be used by [custom comparisons](#custom-comparisons) of fields in which case the
+//! [epsilon type](#epsilon-type) may need to be specified.
+//!
+//! The local definition needs to mirror every field and variant of the remote type with the same
+//! types and the fields need to be accessible.
+//! Fields which should not be compared can be [skipped](#skipping-fields).
+//! Generic arguments of the local definition are passed on to the remote type unless the path
+//! specifies them.
+//! Groups, profiles, mismatch reports and the `rhs` and `epsilon_struct` attributes are not
+//! available for remote types.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! mod geometry {
+//!     #[derive(Clone, Copy, PartialEq, Debug)]
+//!     pub struct Point {
+//!         pub x: f64,
+//!         pub y: f64,
+//!     }
+//! }
+//! use geometry::Point;
+//!
+//! #[derive(AbsDiffEq)]
+//! #[approx(remote = "geometry::Point")]
+//! struct PointDef {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(epsilon_type = f64)]
+//! struct Segment {
+//!     #[approx(with = PointDef)]
+//!     start: Point,
+//!     #[approx(with = PointDef)]
+//!     end: Point,
+//! }
+//!
+//! let p1 = Point { x: 1.0, y: 2.0 };
+//! let p2 = Point { x: 1.01, y: 2.0 };
+//! assert!(PointDef::abs_diff_eq(&p1, &p2, 0.1));
+//!
+//! let s1 = Segment { start: p1, end: p1 };
+//! let s2 = Segment { start: p1, end: p2 };
+//! assert_abs_diff_eq!(s1, s2, epsilon = 0.1);
+//! assert_abs_diff_ne!(s1, s2, epsilon = 0.001);
+//! ```
+//! Omitting fields or variants of the remote type is an error.
+//! ```compile_fail
+//! # use approx_derive::*;
+//! mod geometry {
+//!     pub struct Point {
+//!         pub x: f64,
+//!         pub y: f64,
+//!     }
+//! }
+//!
+//! #[derive(AbsDiffEq)]
+//! #[approx(remote = "geometry::Point")]
+//! // error: the field `y` of the remote type is missing
+//! struct PointDef {
+//!     x: f64,
+//! }
+//! ```
+//! ```compile_fail
+//! # use approx_derive::*;
+//! mod geometry {
+//!     pub enum Shape {
+//!         Circle(f64),
+//!         Square(f64),
+//!     }
+//! }
+//!
+//! #[derive(AbsDiffEq)]
+//! #[approx(remote = "geometry::Shape")]
+//! // error: the variant `Square` of the remote type is missing
+//! enum ShapeDef {
+//!     Circle(f64),
+//! }
+//! ```
+//!
+//! ## Transparent
+//! Newtype wrappers such as `struct Meters(f64)` can forward the traits directly to their single
+//! compared field with `#[approx(transparent)]`.
+//! The epsilon type and the default tolerances are those of the field and the only inferred
+//! bound requires the field to implement the derived trait.
+//! Other fields need to be skipped and the wrapped field can not have approx attributes.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! #[approx(transparent)]
+//! struct Meters(f64);
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(transparent)]
+//! struct Labeled<T> {
+//!     value: T,
+//!     #[approx(skip)]
+//!     label: String,
+//! }
+//!
+//! assert_eq!(Meters::default_epsilon(), f64::EPSILON);
+//! assert_relative_eq!(Meters(100.0), Meters(100.5), max_relative = 0.01);
+//!
+//! let l1 = Labeled { value: 1.0f32, label: "a".into() };
+//! let l2 = Labeled { value: 1.05f32, label: "b".into() };
+//! assert_abs_diff_eq!(l1, l2, epsilon = 0.1);
+//! ```
+//!
+//! ## Conversion
+//! Some types need to be canonicalized as a whole before they can be compared.
+//! With `#[approx(via = Canonical, convert = function)]`, both values are converted by
+//! `function(&value)` into the type `Canonical` which implements the approx traits and the
+//! results are compared instead.
+//! Without `convert`, the conversion `From<&Self>` of the canonical type is used.
+//! Similarly to [transparent](#transparent), the epsilon type and the default tolerances are
+//! those of the canonical type.
+//! Since the fields are not compared, they can not have approx attributes.
+//!
+//! In contrast to [mapping values](#mapping-values) of single fields, this allows different
+//! enum variants to be equal.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(via = u32, convert = time_to_days)]
+//! enum Time {
+//!     Years(u32),
+//!     Months(u32),
+//!     Weeks(u32),
+//!     Days(u32),
+//! }
+//!
+//! fn time_to_days(time: &Time) -> u32 {
+//!     match time {
+//!         Time::Years(y) => 365 * y,
+//!         Time::Months(m) => 30 * m,
+//!         Time::Weeks(w) => 7 * w,
+//!         Time::Days(d) => *d,
+//!     }
+//! }
+//!
+//! assert_abs_diff_eq!(Time::Weeks(1), Time::Days(7));
+//! assert_abs_diff_eq!(Time::Months(1), Time::Weeks(4), epsilon = 2);
+//! assert_abs_diff_ne!(Time::Years(1), Time::Months(12));
+//! ```
+//!
+//! ## Bounds
+//! For generic types, the macros add bounds for every compared field which depends on a type
+//! parameter together with the `Self: PartialEq<Rhs>` bound required by the traits.
+//! Fields compared with `equal` require [PartialEq], fields using `into_iter` require their
+//! items to implement the derived trait and all other fields need to implement the trait with
+//! the inferred `Epsilon` type.
+//! Fields using `map` and `cast_field` do not receive any bounds.
+//! Conversions by `into` and `try_into` require the field or the epsilon type to be convertible
+//! into the target type.
+//!
+//! Similarly to serde, the inferred bounds can be replaced by `#[approx(bound = "...")]`.
+//! On a field, it replaces the bounds of this field while on the object, it replaces all inferred
+//! bounds.
+//! When comparing against a different type with [rhs](#rhs), no bounds are inferred for fields
+//! and generic fields need to be bounded manually.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(epsilon_type = f64)]
+//! struct Measurement<T, U> {
+//!     #[approx(map = |x: &T| Some((*x).into()))]
+//!     #[approx(bound = "T: Into<f64> + Copy")]
+//!     value: T,
+//!     #[approx(into_iter)]
+//!     history: Vec<U>,
+//! }
+//!
+//! let m1 = Measurement { value: 1_f32, history: vec![1.0, 2.0] };
+//! let m2 = Measurement { value: 1.5_f32, history: vec![1.0, 2.1] };
+//!
+//! assert_abs_diff_eq!(m1, m2, epsilon = 0.6);
+//! assert_abs_diff_ne!(m1, m2, epsilon = 0.2);
+//! ```
+//!
 //! # Into Iterator
 //! To compare two fields which consist of a iterable list of values, we can use the
 //! `#[approx(into_iter)]` field attribute.
@@ -501,13 +1174,320 @@
 //! let poly2 = Polynomial { coefficients: vec![1.0, 0.5, 1.0/6.0] };
 //! assert_abs_diff_eq!(poly1, poly2);
 //! ```
+//! If both iterators know their exact length upfront (such as every
+//! [ExactSizeIterator](core::iter::ExactSizeIterator)), differing lengths fail without comparing
+//! any elements.
+//!
+//! ## Iterator Options
+//! The compared elements can be customized by `#[approx(into_iter(...))]`.
+//!
+//! | Option | Functionality |
+//! |:--- | --- |
+//! | `length = strict` | Both iterators need to have the same length (default). |
+//! | `length = shortest` | Compares elements until one of the iterators ends. |
+//! | `skip = n` | Skips the first `n` elements. |
+//! | `stride = k` | Only compares every `k`th element after skipping. |
+//! | `take = n` | Compares at most `n` elements after skipping and striding. |
+//!
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(epsilon_type = f64)]
+//! struct TimeSeries {
+//!     // Only the tail of the series is relevant
+//!     #[approx(into_iter(skip = 3))]
+//!     values: Vec<f64>,
+//!     #[approx(into_iter(length = shortest))]
+//!     forecast: Vec<f64>,
+//! }
+//! let t1 = TimeSeries { values: vec![9.0, 3.0, 1.0, 0.5, 0.2], forecast: vec![0.1] };
+//! let t2 = TimeSeries { values: vec![0.0, 0.0, 0.0, 0.5, 0.2], forecast: vec![0.1, 0.0] };
+//!
+//! assert_abs_diff_eq!(t1, t2);
+//! ```
+//!
+//! ## Norms
+//! Comparing elementwise means that a single component can fail an otherwise equal vector.
+//! With `#[approx(into_iter, norm = l2)]`, the norm of the elementwise difference is compared
+//! against epsilon instead.
+//! For [RelativeEq], the difference may alternatively be within `max_relative` times the larger
+//! norm of both iterators.
+//! The available norms are `l1`, `l2` and `linf`.
+//! [UlpsEq] compares the norm against epsilon only.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! #[approx(epsilon_type = f64)]
+//! struct Velocity {
+//!     #[approx(into_iter, norm = l2)]
+//!     components: [f64; 3],
+//! }
+//! let v1 = Velocity { components: [300.0, 400.0, 1e-9] };
+//! let v2 = Velocity { components: [300.0, 400.0, 2e-9] };
+//!
+//! assert_relative_eq!(v1, v2, epsilon = 0.0, max_relative = 1e-9);
+//! ```
+//! Norms can only be computed for iterators over references to floating point numbers.
+//!
+//! ## Unordered Collections
+//! For sets or lists whose order is not meaningful, `#[approx(into_iter, unordered)]` matches
+//! every element of one collection to a distinct approximately equal element of the other one.
+//! The matching stays correct when the tolerances of multiple elements overlap.
+//! Note that this requires `O(n²)` comparisons and up to `O(n³)` steps to find the matching.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(epsilon_type = f64)]
+//! struct Particles {
+//!     #[approx(into_iter, unordered)]
+//!     positions: Vec<f64>,
+//! }
+//! let p1 = Particles { positions: vec![1.1, 1.0] };
+//! let p2 = Particles { positions: vec![1.0, 1.2] };
+//!
+//! assert_abs_diff_eq!(p1, p2, epsilon = 0.15);
+//! ```
+//!
+//! ## Maps
+//! The iteration order of a [HashMap](std::collections::HashMap) is arbitrary and a
+//! [BTreeMap](std::collections::BTreeMap) yields `(key, value)` pairs.
+//! With `#[approx(map_keys)]`, both maps need to contain exactly the same keys and the values are
+//! compared by key.
+//! The epsilon type is inferred from the value type of the map.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! use std::collections::HashMap;
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Prices {
+//!     #[approx(map_keys)]
+//!     fruits: HashMap<String, f64>,
+//! }
+//! let p1 = Prices { fruits: HashMap::from([("apple".into(), 1.0), ("pear".into(), 2.0)]) };
+//! let p2 = Prices { fruits: HashMap::from([("pear".into(), 2.05), ("apple".into(), 0.95)]) };
+//! let p3 = Prices { fruits: HashMap::from([("apple".into(), 1.0)]) };
+//!
+//! assert_abs_diff_eq!(p1, p2, epsilon = 0.1);
+//! assert_abs_diff_ne!(p1, p3, epsilon = 0.1);
+//! ```
+//! Bounds are only inferred for the value type.
+//! Generic key types require an explicit [`#[approx(bound = "...")]`](#bounds).
+//!
+//! # UlpsEq
+//! The [UlpsEq] derive macro only implements the
+//! [UlpsEq](https://docs.rs/approx/latest/approx/trait.UlpsEq.html) trait itself.
+//! Since it requires [AbsDiffEq] as a supertrait, it has to be combined with either the [AbsDiffEq]
+//! or the [RelativeEq] derive macro.
+//! All field and object attributes described above can be used as well.
+//! In addition, the `max_ulps` value can be controlled with the
+//! `#[approx(static_max_ulps = ...)]` and `#[approx(max_ulps_map = ...)]` field attributes and the
+//! `#[approx(default_max_ulps = ...)]` object attribute.
+//!
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, UlpsEq, PartialEq, Debug)]
+//! #[approx(default_max_ulps = 8)]
+//! struct State {
+//!     time: f64,
+//!     #[approx(static_max_ulps = 0)]
+//!     step: f64,
+//! }
+//!
+//! let s1 = State { time: 1.0, step: 0.1 };
+//! let s2 = State { time: 1.0 + 4.0 * f64::EPSILON, step: 0.1 };
+//! let s3 = State { time: 1.0, step: 0.1 + 2.0 * f64::EPSILON };
+//!
+//! assert_ulps_eq!(s1, s2);
+//! assert_ulps_ne!(s1, s2, max_ulps = 2);
+//! assert_ulps_ne!(s1, s3);
+//! ```
+//!
+//! ## Comparison Method
+//! By default, every field is compared with the method of the implemented trait.
+//! The `#[approx(compare = abs|relative|ulps)]` field attribute selects the method of
+//! [AbsDiffEq], [RelativeEq] or [UlpsEq] for this field in every generated implementation instead.
+//! This is useful for counters or offsets close to zero which should always be compared
+//! absolutely, or for bit-level state which should be compared by ulps.
+//! The comparison also applies to mapped values and the elements of iterators and maps.
+//! Tolerances which are not parameters of the implemented trait fall back to the default values
+//! of the object, i.e. `#[approx(default_max_relative = ...)]` and
+//! `#[approx(default_max_ulps = ...)]`, while `#[approx(static_max_ulps = ...)]` and
+//! `#[approx(static_max_relative = ...)]` define them for a single field.
+//! ```
+//! # use approx::*;
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! struct Calibration {
+//!     #[approx(compare = abs)]
+//!     offset: f32,
+//!     scale: f32,
+//!     #[approx(compare = ulps, static_max_ulps = 2)]
+//!     checksum: f32,
+//! }
+//! let c1 = Calibration { offset: 1.0, scale: 100.0, checksum: 0.5 };
+//! let c2 = Calibration { offset: 1.1, scale: 110.0, checksum: 0.5 };
+//!
+//! assert_relative_eq!(c1, c2, epsilon = 0.15, max_relative = 0.1);
+//! assert_relative_ne!(c1, c2, epsilon = 0.0, max_relative = 0.2);
+//! ```
+//!
+//! # Mismatch Report
+//! When a comparison fails, it is often not obvious which field is responsible.
+//! With `#[approx(mismatches)]`, the derive macros additionally generate the inherent methods
+//! `approx_mismatches(&self, other, epsilon)` and (for [RelativeEq])
+//! `approx_relative_mismatches(&self, other, epsilon, max_relative)`.
+//! They return one entry for every field which is not approximately equal.
+//! The entries have the type `<Name>Mismatch` which is generated next to the derived type with
+//! the same visibility, such as `PathMismatch` for a struct `Path`.
+//! A different name can be chosen by `#[approx(mismatches = Name)]`.
+//! The report only requires the `alloc` crate and can thus be used in `#![no_std]` crates.
+//! Reports are not available for [remote types](#remote-types), [transparent](#transparent)
+//! wrappers and [conversions](#conversion).
+//! Each entry contains
+//! - the `path` of the field including enum variants and indices of iterated fields,
+//! - the `mode` which was used to compare the field (`approx`, `equal`, `map`, `iter`, `cast`,
+//!   `variant` when the enum variants differ or `also` for
+//!   [computed properties](#computed-properties)),
+//! - the compared values `left` and `right` (formatted with [Debug](core::fmt::Debug) if
+//!   possible),
+//! - the effective `epsilon` and `max_relative`.
+//!
+//! Nested types which also generate a report are reported field by field.
+//! ```
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(mismatches)]
+//! struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! #[approx(epsilon_type = f64, mismatches)]
+//! struct Path {
+//!     #[approx(into_iter)]
+//!     points: Vec<Point>,
+//!     #[approx(equal)]
+//!     closed: bool,
+//! }
+//!
+//! let p1 = Path {
+//!     points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }],
+//!     closed: false,
+//! };
+//! let p2 = Path {
+//!     points: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.5 }],
+//!     closed: true,
+//! };
+//!
+//! let mismatches: Vec<PathMismatch> = p1.approx_mismatches(&p2, 0.1);
+//! assert_eq!(mismatches.len(), 2);
+//! assert_eq!(mismatches[0].path, "points[1].y");
+//! assert_eq!(mismatches[0].left, "1.0");
+//! assert_eq!(mismatches[0].right, "1.5");
+//! assert_eq!(mismatches[0].epsilon.as_deref(), Some("0.1"));
+//! assert_eq!(mismatches[1].path, "closed");
+//! assert_eq!(mismatches[1].mode, "equal");
+//! ```
+//!
+//! ## Assertions
+//! The function-like macros [assert_fields_abs_diff_eq!] and [assert_fields_relative_eq!] work
+//! like their counterparts of the approx crate.
+//! Instead of printing both values, they panic with a table of the mismatching fields which
+//! shows the path, both values, their absolute difference and the tolerance.
+//! The table is taken from the [report](#mismatch-report) of types with `#[approx(mismatches)]`.
+//! All other types are shown as a single row with both values formatted by [Debug].
+//! The optional arguments `epsilon = ...` and `max_relative = ...` can be specified after both
+//! values.
+//! ```should_panic
+//! # use approx_derive::*;
+//! #[derive(RelativeEq, PartialEq, Debug)]
+//! #[approx(mismatches)]
+//! struct Point {
+//!     x: f64,
+//!     y: f64,
+//! }
+//!
+//! let p1 = Point { x: 1.0, y: 2.0 };
+//! let p2 = Point { x: 1.0, y: 2.5 };
+//!
+//! assert_fields_relative_eq!(p1, p2, max_relative = 0.25);
+//! // Panics with
+//! // assertion failed: `assert_fields_abs_diff_eq!(p1, p2, epsilon = 0.1)`
+//! //
+//! // path | left | right | |diff| | tolerance
+//! // -----+------+-------+--------+----------
+//! // y    | 2.0  | 2.5   | 0.5    | 0.1
+//! assert_fields_abs_diff_eq!(p1, p2, epsilon = 0.1);
+//! ```
+//!
+//! # Attribute Validation
+//! Only attributes of the form `#[approx(...)]` are considered.
+//! Unknown or malformed arguments result in a compile error which suggests the closest valid
+//! argument.
+//! ```compile_fail
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! // error: unknown approx attribute `epsilon_typ`, did you mean `epsilon_type`?
+//! #[approx(epsilon_typ = f32)]
+//! struct Length(f32);
+//! ```
+//! Specifying the same argument twice or combining arguments which contradict each other is an
+//! error as well.
+//! Arguments of an enum variant apply to all of its fields and are checked together with the
+//! arguments of each field, which take precedence.
+//! A skipped field can not be compared with `equal`, `map`, `cast_field`, `cast_value`,
+//! `into_iter`, `map_keys`, `with` or `compare_with` and a field can only use one of `cast_field`
+//! and `cast_value`.
+//! The custom comparisons `with` and `compare_with` can not be combined with each other or with
+//! `equal`, `map`, `into_iter` and `map_keys`.
+//! A field compared with `equal` can not use `map`, `cast_field`, `cast_value`, `into_iter` or
+//! `map_keys` and `map` excludes `into_iter` and `map_keys`.
+//! A `periodic` field can not use `skip`, `equal`, `map`, `with`, `compare_with` or `norm`.
+//! The comparison method selected by `compare` can not be combined with `skip`, `equal`, `with`
+//! or `compare_with` and the tolerance expressions `epsilon` and `max_relative` exclude their
+//! static and mapped counterparts.
+//! The conditions `skip_if` and `compare_if` exclude each other and `skip`.
+//! Similarly, `into_iter` and `map_keys` exclude each other and `unordered` and `norm` can only be
+//! used together with `into_iter` but not with each other.
+//! ```compile_fail
+//! # use approx_derive::*;
+//! #[derive(AbsDiffEq, PartialEq, Debug)]
+//! struct Length {
+//!     value: f32,
+//!     #[approx(skip)]
+//!     // error: `skip` can not be combined with `equal`
+//!     #[approx(equal)]
+//!     unit: String,
+//! }
+//! ```
 
 mod abs_diff_eq;
+mod also;
 mod args_parsing;
+mod assert_fields;
 mod base_types;
+mod bounds;
+mod compare;
+mod epsilon_struct;
+mod groups;
+mod into_iter;
+mod mismatches;
+mod periodic;
+mod profiles;
 mod rel_diff_eq;
+mod remote;
+mod special_values;
+mod transparent;
+mod ulps_eq;
 
 use args_parsing::*;
+use assert_fields::*;
 use base_types::*;
 
 struct AbsDiffEqParser {
@@ -519,15 +1499,85 @@ struct AbsDiffEqParser {
 #[proc_macro_derive(AbsDiffEq, attributes(approx))]
 pub fn derive_abs_diff_eq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let parsed = syn::parse_macro_input!(input as AbsDiffEqParser);
-    parsed.implement_derive_abs_diff_eq().into()
+    if parsed.struct_args.remote.is_some() {
+        return parsed.implement_remote(ApproxTrait::Abs).into();
+    }
+    if parsed.uses_forwarding() {
+        return parsed.implement_forwarding(ApproxTrait::Abs).into();
+    }
+    let mut output = quote::quote!();
+    output.extend(parsed.implement_derive_abs_diff_eq());
+    output.extend(parsed.implement_mismatch_struct());
+    output.extend(parsed.implement_mismatches(ApproxTrait::Abs));
+    output.extend(parsed.implement_groups(ApproxTrait::Abs));
+    output.extend(parsed.implement_profile_enum());
+    output.extend(parsed.implement_profiles(ApproxTrait::Abs));
+    output.into()
 }
 
 /// See the [crate] level documentation for a guide.
 #[proc_macro_derive(RelativeEq, attributes(approx))]
 pub fn derive_rel_diff_eq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let parsed = syn::parse_macro_input!(input as AbsDiffEqParser);
+    if parsed.struct_args.remote.is_some() {
+        let mut output = parsed.implement_remote(ApproxTrait::Abs);
+        output.extend(parsed.implement_remote(ApproxTrait::Relative));
+        return output.into();
+    }
+    if parsed.uses_forwarding() {
+        let mut output = parsed.implement_forwarding(ApproxTrait::Abs);
+        output.extend(parsed.implement_forwarding(ApproxTrait::Relative));
+        return output.into();
+    }
     let mut output = quote::quote!();
     output.extend(parsed.implement_derive_abs_diff_eq());
+    output.extend(parsed.implement_mismatch_struct());
+    output.extend(parsed.implement_mismatches(ApproxTrait::Abs));
+    output.extend(parsed.implement_groups(ApproxTrait::Abs));
     output.extend(parsed.implement_derive_rel_diff_eq());
+    output.extend(parsed.implement_mismatches(ApproxTrait::Relative));
+    output.extend(parsed.implement_groups(ApproxTrait::Relative));
+    output.extend(parsed.implement_profile_enum());
+    output.extend(parsed.implement_profiles(ApproxTrait::Abs));
+    output.extend(parsed.implement_profiles(ApproxTrait::Relative));
     output.into()
 }
+
+/// See the [crate] level documentation for a guide.
+#[proc_macro_derive(UlpsEq, attributes(approx))]
+pub fn derive_ulps_eq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let parsed = syn::parse_macro_input!(input as AbsDiffEqParser);
+    if parsed.struct_args.remote.is_some() {
+        return parsed.implement_remote(ApproxTrait::Ulps).into();
+    }
+    if parsed.uses_forwarding() {
+        return parsed.implement_forwarding(ApproxTrait::Ulps).into();
+    }
+    let mut output = quote::quote!();
+    output.extend(parsed.implement_derive_ulps_eq());
+    output.extend(parsed.implement_groups(ApproxTrait::Ulps));
+    output.extend(parsed.implement_profiles(ApproxTrait::Ulps));
+    output.into()
+}
+
+/// Asserts that two values are equal in the sense of [AbsDiffEq] and prints a table of all
+/// mismatching fields otherwise.
+/// See the [crate] level documentation for a guide.
+#[proc_macro]
+pub fn assert_fields_abs_diff_eq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(input as AssertFieldsArgs);
+    args.implement_assert_fields(ApproxTrait::Abs, "assert_fields_abs_diff_eq")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Asserts that two values are equal in the sense of [RelativeEq] and prints a table of all
+/// mismatching fields otherwise.
+/// See the [crate] level documentation for a guide.
+#[proc_macro]
+pub fn assert_fields_relative_eq(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let args = syn::parse_macro_input!(input as AssertFieldsArgs);
+    args.implement_assert_fields(ApproxTrait::Relative, "assert_fields_relative_eq")
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
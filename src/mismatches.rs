@@ -0,0 +1,777 @@
+use crate::also::*;
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::compare::*;
+use crate::into_iter::*;
+use crate::periodic::*;
+use crate::AbsDiffEqParser;
+
+/// Wraps a reference to a value such that its [Debug] representation is used whenever possible.
+fn debug_string(value: proc_macro2::TokenStream) -> proc_macro2::TokenStream {
+    quote::quote!((&__ApproxDebug(#value)).__approx_format())
+}
+
+/// Formats the absolute difference of two references to floating point numbers.
+fn abs_diff_string(
+    left: proc_macro2::TokenStream,
+    right: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    quote::quote!((&__ApproxPair(#left, #right)).__approx_abs_diff())
+}
+
+impl AbsDiffEqParser {
+    fn get_mismatches_method(approx_trait: ApproxTrait) -> syn::Ident {
+        match approx_trait {
+            ApproxTrait::Relative => quote::format_ident!("approx_relative_mismatches"),
+            _ => quote::format_ident!("approx_mismatches"),
+        }
+    }
+
+    /// Name of the report entry given by `#[approx(mismatches = Name)]` or such as
+    /// `StateMismatch` for the struct `State`
+    pub fn get_mismatch_ident(&self) -> syn::Ident {
+        match &self.struct_args.mismatches {
+            Some(Some(name)) => name.clone(),
+            _ => quote::format_ident!("{}Mismatch", self.base_type.ident()),
+        }
+    }
+
+    /// Imports of the `alloc` crate which the report uses instead of the prelude of `std` such
+    /// that it also works in `#![no_std]` crates.
+    fn get_alloc_imports() -> proc_macro2::TokenStream {
+        quote::quote!(
+            extern crate alloc;
+            #[allow(unused_imports)]
+            use alloc::{format, string::String, vec, vec::Vec};
+        )
+    }
+
+    /// Defines the type of the entries returned by the report if it was requested by
+    /// `#[approx(mismatches)]`.
+    ///
+    /// The type is shared by `approx_mismatches` and `approx_relative_mismatches`.
+    /// It is defined in a hidden module which imports the `alloc` crate and re-exported with the
+    /// visibility of the derived type.
+    pub fn implement_mismatch_struct(&self) -> proc_macro2::TokenStream {
+        if self.struct_args.mismatches.is_none() {
+            return quote::quote!();
+        }
+        let vis = self.base_type.vis();
+        let mismatch = self.get_mismatch_ident();
+        let module = quote::format_ident!("__approx_{}", mismatch);
+        let alloc_imports = Self::get_alloc_imports();
+        let doc = format!(
+            "A single field of `{}` which is not approximately equal",
+            self.base_type.ident()
+        );
+        quote::quote!(
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            mod #module {
+                #alloc_imports
+
+                #[doc = #doc]
+                #[derive(Clone, Debug, PartialEq)]
+                pub struct #mismatch {
+                    /// Path of the field such as `position.x`, `Variant.0`, `values[3]` or
+                    /// `prices["apple"]`
+                    pub path: String,
+                    /// How the field was compared: `approx`, `equal`, `with`, `map`, `keys`,
+                    /// `norm`, `iter`, `periodic`, `cast`, `variant` or `also` for computed
+                    /// properties
+                    pub mode: &'static str,
+                    /// Compared value of `self`
+                    pub left: String,
+                    /// Compared value of `other`
+                    pub right: String,
+                    /// Absolute difference of both values if they are floating point numbers or
+                    /// the norm of the difference of two iterators
+                    pub diff: Option<String>,
+                    /// Effective epsilon used for this field
+                    pub epsilon: Option<String>,
+                    /// Effective `max_relative` used for this field
+                    pub max_relative: Option<String>,
+                }
+            }
+            #vis use #module::#mismatch;
+        )
+    }
+
+    /// Items which are used by the generated report.
+    ///
+    /// The entries of the report are referred to as `ApproxMismatch`.
+    /// Nested reports are picked up by method resolution: the inherent method of a derived type
+    /// takes precedence over the fallback trait which is implemented for all other types.
+    /// Similarly, values are formatted with [Debug] if they implement it.
+    fn get_mismatch_helpers(&self, approx_trait: ApproxTrait) -> proc_macro2::TokenStream {
+        let method = Self::get_mismatches_method(approx_trait);
+        let mismatch = self.get_mismatch_ident();
+        let tolerance_generics = match approx_trait {
+            ApproxTrait::Relative => quote::quote!(E, M),
+            _ => quote::quote!(E),
+        };
+        let tolerance_args = match approx_trait {
+            ApproxTrait::Relative => quote::quote!(_epsilon: E, _max_relative: M),
+            _ => quote::quote!(_epsilon: E),
+        };
+        let alloc_imports = Self::get_alloc_imports();
+        quote::quote!(
+            #alloc_imports
+            type ApproxMismatch = #mismatch;
+
+            trait __ApproxMismatchesFallback {
+                fn #method<O, #tolerance_generics>(
+                    &self,
+                    _other: O,
+                    #tolerance_args
+                ) -> Vec<ApproxMismatch> {
+                    Vec::new()
+                }
+            }
+            impl<T: ?Sized> __ApproxMismatchesFallback for T {}
+
+            struct __ApproxDebug<'a, T: ?Sized>(&'a T);
+            trait __ApproxDebugFormat {
+                fn __approx_format(&self) -> String;
+            }
+            impl<T: ?Sized + core::fmt::Debug> __ApproxDebugFormat for __ApproxDebug<'_, T> {
+                fn __approx_format(&self) -> String {
+                    format!("{:?}", self.0)
+                }
+            }
+            trait __ApproxDebugFallback {
+                fn __approx_format(&self) -> String;
+            }
+            impl<T: ?Sized> __ApproxDebugFallback for &__ApproxDebug<'_, T> {
+                fn __approx_format(&self) -> String {
+                    String::from("..")
+                }
+            }
+
+            struct __ApproxPair<'a, A: ?Sized, B: ?Sized>(&'a A, &'a B);
+            trait __ApproxAbsDiff {
+                fn __approx_abs_diff(&self) -> Option<String>;
+            }
+            impl __ApproxAbsDiff for __ApproxPair<'_, f32, f32> {
+                fn __approx_abs_diff(&self) -> Option<String> {
+                    let (a, b) = (*self.0, *self.1);
+                    Some(format!("{:?}", if a > b { a - b } else { b - a }))
+                }
+            }
+            impl __ApproxAbsDiff for __ApproxPair<'_, f64, f64> {
+                fn __approx_abs_diff(&self) -> Option<String> {
+                    let (a, b) = (*self.0, *self.1);
+                    Some(format!("{:?}", if a > b { a - b } else { b - a }))
+                }
+            }
+            impl __ApproxAbsDiff for __ApproxPair<'_, &f32, &f32> {
+                fn __approx_abs_diff(&self) -> Option<String> {
+                    let (a, b) = (**self.0, **self.1);
+                    Some(format!("{:?}", if a > b { a - b } else { b - a }))
+                }
+            }
+            impl __ApproxAbsDiff for __ApproxPair<'_, &f64, &f64> {
+                fn __approx_abs_diff(&self) -> Option<String> {
+                    let (a, b) = (**self.0, **self.1);
+                    Some(format!("{:?}", if a > b { a - b } else { b - a }))
+                }
+            }
+            trait __ApproxAbsDiffFallback {
+                fn __approx_abs_diff(&self) -> Option<String>;
+            }
+            impl<A: ?Sized, B: ?Sized> __ApproxAbsDiffFallback for &__ApproxPair<'_, A, B> {
+                fn __approx_abs_diff(&self) -> Option<String> {
+                    None
+                }
+            }
+
+            fn __approx_join(path: &str, nested: &str) -> String {
+                if nested.is_empty() {
+                    String::from(path)
+                } else if nested.starts_with('[') {
+                    format!("{}{}", path, nested)
+                } else {
+                    format!("{}.{}", path, nested)
+                }
+            }
+        )
+    }
+
+    /// Statements which push the mismatches of a single field onto `mismatches`.
+    fn get_field_mismatches(
+        &self,
+        approx_trait: ApproxTrait,
+        n: usize,
+        field_with_args: &FieldWithArgs,
+        idents: Option<(syn::Ident, syn::Ident)>,
+        path: String,
+    ) -> Option<proc_macro2::TokenStream> {
+        let check =
+            self.get_single_field_check(approx_trait, n, field_with_args, idents.clone())?;
+        let FieldFormatted {
+            own_field,
+            other_field,
+            epsilon,
+            max_relative,
+            max_ulps,
+            mapping,
+            set_equal,
+            use_iterator,
+            use_map_keys,
+            compare_with,
+            fallible_cast,
+            ..
+        } = self.format_nth_field(n, field_with_args, idents)?;
+
+        let method = Self::get_mismatches_method(approx_trait);
+        let (tolerances, max_relative_string) = match approx_trait {
+            ApproxTrait::Relative => {
+                let max_relative_string = debug_string(quote::quote!(&#max_relative));
+                (
+                    quote::quote!(#epsilon, #max_relative),
+                    quote::quote!(Some(#max_relative_string)),
+                )
+            }
+            _ => (quote::quote!(#epsilon), quote::quote!(None)),
+        };
+        let epsilon_string = debug_string(quote::quote!(&#epsilon));
+        // Compares the references `a` and `b` to elements of iterators and maps
+        let element_check = get_element_check(
+            field_with_args.args.compare.unwrap_or(approx_trait),
+            &field_with_args.args,
+            quote::quote!(a),
+            quote::quote!(b),
+            &epsilon,
+            &max_relative,
+            &max_ulps,
+        );
+        let mode = if set_equal {
+            "equal"
+        } else if compare_with.is_some() {
+            "with"
+        } else if mapping.is_some() {
+            "map"
+        } else if use_map_keys {
+            "keys"
+        } else if use_iterator && field_with_args.args.norm.is_some() {
+            "norm"
+        } else if use_iterator {
+            "iter"
+        } else if field_with_args.args.periodic.is_some() {
+            "periodic"
+        } else if field_with_args.args.cast_strategy.is_some() {
+            "cast"
+        } else {
+            "approx"
+        };
+        // Pushes the nested report of two values or a single entry if there is none
+        let push_nested = |left: proc_macro2::TokenStream,
+                           right: proc_macro2::TokenStream,
+                           path: proc_macro2::TokenStream| {
+            let left_string = debug_string(quote::quote!(&#left));
+            let right_string = debug_string(quote::quote!(&#right));
+            let diff = abs_diff_string(quote::quote!(&#left), quote::quote!(&#right));
+            quote::quote!(
+                let path = #path;
+                let (left, right, diff) = (#left_string, #right_string, #diff);
+                let nested = (#left).#method(#right, #tolerances);
+                if nested.is_empty() {
+                    mismatches.push(ApproxMismatch {
+                        path,
+                        mode: #mode,
+                        left,
+                        right,
+                        diff,
+                        epsilon: Some(#epsilon_string),
+                        max_relative: #max_relative_string,
+                    });
+                } else {
+                    mismatches.extend(nested.into_iter().map(|m| ApproxMismatch {
+                        path: __approx_join(&path, &m.path),
+                        mode: m.mode,
+                        left: m.left,
+                        right: m.right,
+                        diff: m.diff,
+                        epsilon: m.epsilon,
+                        max_relative: m.max_relative,
+                    }));
+                }
+            )
+        };
+
+        let details = if set_equal {
+            let left_string = debug_string(own_field.clone());
+            let right_string = debug_string(other_field.clone());
+            quote::quote!(
+                mismatches.push(ApproxMismatch {
+                    path: String::from(#path),
+                    mode: #mode,
+                    left: #left_string,
+                    right: #right_string,
+                    diff: None,
+                    epsilon: None,
+                    max_relative: None,
+                });
+            )
+        } else if let Some(map) = mapping {
+            let left_string = debug_string(quote::quote!(&a));
+            let right_string = debug_string(quote::quote!(&b));
+            let diff = abs_diff_string(quote::quote!(a), quote::quote!(b));
+            quote::quote!(
+                let (a, b) = ((#map)(#own_field), (#map)(#other_field));
+                mismatches.push(ApproxMismatch {
+                    path: String::from(#path),
+                    mode: #mode,
+                    left: #left_string,
+                    right: #right_string,
+                    diff: match (&a, &b) {
+                        (Some(a), Some(b)) => #diff,
+                        _ => None,
+                    },
+                    epsilon: Some(#epsilon_string),
+                    max_relative: #max_relative_string,
+                });
+            )
+        } else if use_map_keys {
+            let push_value = push_nested(
+                quote::quote!(a),
+                quote::quote!(b),
+                quote::quote!(format!("{}[{:?}]", #path, key)),
+            );
+            let missing = |key, left, right| {
+                quote::quote!(
+                    mismatches.push(ApproxMismatch {
+                        path: format!("{}[{:?}]", #path, #key),
+                        mode: #mode,
+                        left: #left,
+                        right: #right,
+                        diff: None,
+                        epsilon: None,
+                        max_relative: None,
+                    });
+                )
+            };
+            let missing_right = missing(
+                quote::quote!(key),
+                debug_string(quote::quote!(a)),
+                quote::quote!(String::from("<missing>")),
+            );
+            let missing_left = missing(
+                quote::quote!(key),
+                quote::quote!(String::from("<missing>")),
+                debug_string(quote::quote!(b)),
+            );
+            quote::quote!(
+                let (map1, map2) = (#own_field, #other_field);
+                for (key, a) in map1.iter() {
+                    match map2.get(key) {
+                        Some(b) => {
+                            if !#element_check {
+                                #push_value
+                            }
+                        }
+                        None => {
+                            #missing_right
+                        }
+                    }
+                }
+                for (key, b) in map2.iter() {
+                    if map1.get(key).is_none() {
+                        #missing_left
+                    }
+                }
+            )
+        } else if let (true, Some(norm)) = (use_iterator, field_with_args.args.norm) {
+            let norms = get_norms(&own_field, &other_field, &field_with_args.args, norm);
+            let left_string = debug_string(own_field.clone());
+            let right_string = debug_string(other_field.clone());
+            let diff_string = debug_string(quote::quote!(&diff));
+            quote::quote!(
+                let norms = #norms;
+                let (left, right) = match norms {
+                    Some(_) => (#left_string, #right_string),
+                    None => (String::from("different length"), String::from("different length")),
+                };
+                mismatches.push(ApproxMismatch {
+                    path: String::from(#path),
+                    mode: #mode,
+                    left,
+                    right,
+                    diff: match norms {
+                        Some((Some(diff), _, _)) => Some(#diff_string),
+                        _ => None,
+                    },
+                    epsilon: Some(#epsilon_string),
+                    max_relative: #max_relative_string,
+                });
+            )
+        } else if use_iterator && field_with_args.args.unordered.unwrap_or(false) {
+            let matching_helper = get_matching_helper();
+            let options = field_with_args.args.iter_options.as_ref();
+            let window = get_iter_window(options);
+            let unmatched = |index, left, right| {
+                let index = get_iter_index(options, index);
+                quote::quote!(
+                    mismatches.push(ApproxMismatch {
+                        path: format!("{}[{}]", #path, #index),
+                        mode: #mode,
+                        left: #left,
+                        right: #right,
+                        diff: None,
+                        epsilon: Some(#epsilon_string),
+                        max_relative: #max_relative_string,
+                    });
+                )
+            };
+            let unmatched_left = unmatched(
+                quote::quote!(i),
+                debug_string(quote::quote!(a)),
+                quote::quote!(String::from("<unmatched>")),
+            );
+            let unmatched_right = unmatched(
+                quote::quote!(j),
+                quote::quote!(String::from("<unmatched>")),
+                debug_string(quote::quote!(b)),
+            );
+            quote::quote!(
+                #matching_helper
+                let items1: alloc::vec::Vec<_> =
+                    core::iter::IntoIterator::into_iter(#own_field)#window.collect();
+                let items2: alloc::vec::Vec<_> =
+                    core::iter::IntoIterator::into_iter(#other_field)#window.collect();
+                if items1.len() != items2.len() {
+                    mismatches.push(ApproxMismatch {
+                        path: String::from(#path),
+                        mode: #mode,
+                        left: format!("{} elements", items1.len()),
+                        right: format!("{} elements", items2.len()),
+                        diff: None,
+                        epsilon: Some(#epsilon_string),
+                        max_relative: #max_relative_string,
+                    });
+                } else {
+                    let adjacency: alloc::vec::Vec<alloc::vec::Vec<bool>> = items1
+                        .iter()
+                        .map(|a| items2.iter().map(|b| #element_check).collect())
+                        .collect();
+                    let matched = __approx_matching(&adjacency);
+                    for (i, a) in items1.iter().enumerate() {
+                        if !matched.contains(&Some(i)) {
+                            #unmatched_left
+                        }
+                    }
+                    for (j, b) in items2.iter().enumerate() {
+                        if matched[j].is_none() {
+                            #unmatched_right
+                        }
+                    }
+                }
+            )
+        } else if use_iterator {
+            let options = field_with_args.args.iter_options.as_ref();
+            let window = get_iter_window(options);
+            let index = get_iter_index(options, quote::quote!(index));
+            let push_element = push_nested(
+                quote::quote!(a),
+                quote::quote!(b),
+                quote::quote!(format!("{}[{}]", #path, #index)),
+            );
+            let shortest = options.is_some_and(|x| x.is_shortest());
+            quote::quote!(
+                let mut iter1 = core::iter::IntoIterator::into_iter(#own_field)#window;
+                let mut iter2 = core::iter::IntoIterator::into_iter(#other_field)#window;
+                let mut index = 0usize;
+                // Lengths which are known upfront are reported without comparing elements
+                let known_lengths = match (iter1.size_hint(), iter2.size_hint()) {
+                    ((low1, Some(high1)), (low2, Some(high2)))
+                        if !#shortest && low1 == high1 && low2 == high2 && low1 != low2 =>
+                    {
+                        Some((low1, low2))
+                    }
+                    _ => None,
+                };
+                if let Some((len1, len2)) = known_lengths {
+                    mismatches.push(ApproxMismatch {
+                        path: String::from(#path),
+                        mode: #mode,
+                        left: format!("{} elements", len1),
+                        right: format!("{} elements", len2),
+                        diff: None,
+                        epsilon: Some(#epsilon_string),
+                        max_relative: #max_relative_string,
+                    });
+                }
+                while known_lengths.is_none() {
+                    match (iter1.next(), iter2.next()) {
+                        (None, None) => break,
+                        (Some(a), Some(b)) => {
+                            if !{
+                                let (a, b) = (&a, &b);
+                                #element_check
+                            } {
+                                #push_element
+                            }
+                        }
+                        _ if #shortest => break,
+                        (a, b) => {
+                            let len1 = index + a.iter().count() + iter1.count();
+                            let len2 = index + b.iter().count() + iter2.count();
+                            mismatches.push(ApproxMismatch {
+                                path: String::from(#path),
+                                mode: #mode,
+                                left: format!("{} elements", len1),
+                                right: format!("{} elements", len2),
+                                diff: None,
+                                epsilon: Some(#epsilon_string),
+                                max_relative: #max_relative_string,
+                            });
+                            break;
+                        }
+                    }
+                    index += 1;
+                }
+            )
+        } else if let Some(period) = &field_with_args.args.periodic {
+            let left_string = debug_string(own_field.clone());
+            let right_string = debug_string(other_field.clone());
+            let diff = get_periodic_diff(own_field.clone(), other_field.clone(), period);
+            let diff_string = debug_string(quote::quote!(&#diff));
+            quote::quote!(
+                mismatches.push(ApproxMismatch {
+                    path: String::from(#path),
+                    mode: #mode,
+                    left: #left_string,
+                    right: #right_string,
+                    diff: Some(#diff_string),
+                    epsilon: Some(#epsilon_string),
+                    max_relative: #max_relative_string,
+                });
+            )
+        } else if compare_with.is_some() || field_with_args.args.cast_strategy.is_some() {
+            let left_string = debug_string(own_field.clone());
+            let right_string = debug_string(other_field.clone());
+            let diff = abs_diff_string(own_field.clone(), other_field.clone());
+            quote::quote!(
+                mismatches.push(ApproxMismatch {
+                    path: String::from(#path),
+                    mode: #mode,
+                    left: #left_string,
+                    right: #right_string,
+                    diff: #diff,
+                    epsilon: Some(#epsilon_string),
+                    max_relative: #max_relative_string,
+                });
+            )
+        } else {
+            push_nested(own_field, other_field, quote::quote!(String::from(#path)))
+        };
+        // Failed conversions by `try_into` report the unconverted values
+        let details = match fallible_cast {
+            Some((left, right)) => {
+                let left_string = debug_string(left);
+                let right_string = debug_string(right);
+                let converted = with_fallible_cast(true, quote::quote!({ #details true }));
+                quote::quote!(
+                    if !#converted {
+                        mismatches.push(ApproxMismatch {
+                            path: String::from(#path),
+                            mode: #mode,
+                            left: #left_string,
+                            right: #right_string,
+                            diff: None,
+                            epsilon: None,
+                            max_relative: None,
+                        });
+                    }
+                )
+            }
+            None => details,
+        };
+
+        Some(quote::quote!(
+            if !(#check) {
+                #details
+            }
+        ))
+    }
+
+    /// Statements which push a computed property given by `#[approx(also(...))]` onto
+    /// `mismatches` if it is not approximately equal.
+    fn get_computed_property_mismatches(
+        &self,
+        approx_trait: ApproxTrait,
+        property: &ComputedProperty,
+    ) -> proc_macro2::TokenStream {
+        let values = get_computed_values(property);
+        let (epsilon, max_relative, max_ulps) = get_computed_tolerances(property);
+        let check = get_trait_comparison(
+            approx_trait,
+            None,
+            quote::quote!(&a),
+            quote::quote!(&b),
+            &epsilon,
+            &max_relative,
+            &max_ulps,
+        );
+        let path = property.name.to_string();
+        let left_string = debug_string(quote::quote!(&a));
+        let right_string = debug_string(quote::quote!(&b));
+        let diff = abs_diff_string(quote::quote!(&a), quote::quote!(&b));
+        let epsilon_string = debug_string(quote::quote!(&#epsilon));
+        let max_relative_string = match approx_trait {
+            ApproxTrait::Relative => {
+                let max_relative_string = debug_string(quote::quote!(&#max_relative));
+                quote::quote!(Some(#max_relative_string))
+            }
+            _ => quote::quote!(None),
+        };
+        quote::quote!({
+            #values
+            if !#check {
+                mismatches.push(ApproxMismatch {
+                    path: String::from(#path),
+                    mode: "also",
+                    left: #left_string,
+                    right: #right_string,
+                    diff: #diff,
+                    epsilon: Some(#epsilon_string),
+                    max_relative: #max_relative_string,
+                });
+            }
+        })
+    }
+
+    fn get_field_path(n: usize, field_with_args: &FieldWithArgs) -> String {
+        match &field_with_args.ident {
+            Some(ident) => ident.to_string().trim_start_matches("r#").to_string(),
+            None => n.to_string(),
+        }
+    }
+
+    /// Generates an inherent method which lists all fields that are not approximately equal if
+    /// it was requested by `#[approx(mismatches)]`.
+    pub fn implement_mismatches(&self, approx_trait: ApproxTrait) -> proc_macro2::TokenStream {
+        if self.struct_args.mismatches.is_none() {
+            return quote::quote!();
+        }
+        let obj_name = &self.base_type.ident();
+        let (impl_generics, ty_generics, _) = self.base_type.generics().split_for_impl();
+        let where_clause = self.generate_where_clause(approx_trait);
+        let rhs_type = self.get_rhs_type();
+        let trait_rhs = self.get_trait_rhs();
+        let helpers = self.get_mismatch_helpers(approx_trait);
+        let special_values = self.get_special_values_helpers_if_used();
+        let missing_tolerances = self.get_missing_tolerances(approx_trait);
+
+        let method = Self::get_mismatches_method(approx_trait);
+        let (tolerances, doc) = match approx_trait {
+            ApproxTrait::Relative => (
+                quote::quote!(
+                    epsilon: <Self as #ApproxName::AbsDiffEq #trait_rhs>::Epsilon,
+                    max_relative: <Self as #ApproxName::AbsDiffEq #trait_rhs>::Epsilon
+                ),
+                "Lists all fields which are not equal in the sense of `RelativeEq`",
+            ),
+            _ => (
+                quote::quote!(epsilon: <Self as #ApproxName::AbsDiffEq #trait_rhs>::Epsilon),
+                "Lists all fields which are not equal in the sense of `AbsDiffEq`",
+            ),
+        };
+
+        let body = match &self.base_type {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => {
+                let fields =
+                    fields_with_args
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(n, field_with_args)| {
+                            let path = Self::get_field_path(n, field_with_args);
+                            self.get_field_mismatches(approx_trait, n, field_with_args, None, path)
+                        });
+                quote::quote!(#(#fields)*)
+            }
+            #[allow(unused)]
+            BaseType::Enum {
+                item_enum,
+                variants_with_args,
+            } => {
+                let variants = variants_with_args.iter().map(|variant_with_args| {
+                    let (pattern1, pattern2, idents) = variant_with_args.get_match_patterns();
+                    let fields = variant_with_args
+                        .fields_with_args
+                        .iter()
+                        .enumerate()
+                        .zip(idents)
+                        .filter_map(|((n, field_with_args), idents)| {
+                            let path = format!(
+                                "{}.{}",
+                                variant_with_args.ident,
+                                Self::get_field_path(n, field_with_args)
+                            );
+                            self.get_field_mismatches(
+                                approx_trait,
+                                0,
+                                field_with_args,
+                                Some(idents),
+                                path,
+                            )
+                        });
+                    quote::quote!((#pattern1, #pattern2) => { #(#fields)* })
+                });
+                let variant_names = variants_with_args.iter().map(|variant_with_args| {
+                    let variant = &variant_with_args.ident;
+                    let name = variant.to_string();
+                    quote::quote!(Self::#variant { .. } => #name)
+                });
+                quote::quote!(
+                    let variant_name = |value: &Self| match value {
+                        #(#variant_names,)*
+                    };
+                    match (self, other) {
+                        #(#variants)*
+                        _ => mismatches.push(ApproxMismatch {
+                            path: String::new(),
+                            mode: "variant",
+                            left: String::from(variant_name(self)),
+                            right: String::from(variant_name(other)),
+                            diff: None,
+                            epsilon: None,
+                            max_relative: None,
+                        }),
+                    }
+                )
+            }
+        };
+
+        let properties = self
+            .struct_args
+            .also
+            .iter()
+            .map(|property| self.get_computed_property_mismatches(approx_trait, property));
+
+        quote::quote!(
+            const _: () = {
+                #helpers
+                #special_values
+
+                #[automatically_derived]
+                impl #impl_generics #obj_name #ty_generics
+                #where_clause
+                {
+                    #[doc = #doc]
+                    pub fn #method(&self, other: &#rhs_type, #tolerances) -> Vec<ApproxMismatch> {
+                        #missing_tolerances
+                        let mut mismatches = Vec::new();
+                        #body
+                        #(#properties)*
+                        mismatches
+                    }
+                }
+            };
+        )
+    }
+}
@@ -0,0 +1,41 @@
+/// Absolute difference of two references to values in a periodic domain.
+///
+/// The minimal image of the difference is used such that values close to the wrap point are
+/// close to each other, i.e. `359.9` and `0.1` differ by `0.2` with a period of `360`.
+pub fn get_periodic_diff(
+    a: proc_macro2::TokenStream,
+    b: proc_macro2::TokenStream,
+    period: &syn::Expr,
+) -> proc_macro2::TokenStream {
+    quote::quote!({
+        let (a, b) = (*#a, *#b);
+        let period = #period;
+        let diff = a - b;
+        (diff - period * (diff / period).round()).abs()
+    })
+}
+
+/// Compares two references to values in a periodic domain.
+///
+/// For [RelativeEq](https://docs.rs/approx/latest/approx/trait.RelativeEq.html), the difference
+/// may alternatively be within `max_relative` times the larger magnitude of both values.
+pub fn get_periodic_comparison(
+    a: proc_macro2::TokenStream,
+    b: proc_macro2::TokenStream,
+    period: &syn::Expr,
+    epsilon: &proc_macro2::TokenStream,
+    max_relative: Option<&proc_macro2::TokenStream>,
+) -> proc_macro2::TokenStream {
+    let diff = get_periodic_diff(a.clone(), b.clone(), period);
+    let relative = max_relative.map(|max_relative| {
+        quote::quote!(|| {
+            let (a, b) = ((*#a).abs(), (*#b).abs());
+            let largest = if a > b { a } else { b };
+            diff <= largest * #max_relative
+        })
+    });
+    quote::quote!(({
+        let diff = #diff;
+        diff <= #epsilon #relative
+    }))
+}
@@ -0,0 +1,200 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::AbsDiffEqParser;
+
+/// Converts the name of a profile such as `gpu_offload` to the variant `GpuOffload`
+fn get_profile_variant(name: &syn::Ident) -> syn::Ident {
+    let variant: String = name
+        .to_string()
+        .split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    syn::Ident::new(&variant, name.span())
+}
+
+/// The field with the static tolerances it defines for the given profile
+fn get_profile_field(field_with_args: &FieldWithArgs, profile: &syn::Ident) -> FieldWithArgs {
+    let mut field_with_args = field_with_args.clone();
+    let args = &mut field_with_args.args;
+    if let Some(overrides) = args.profiles.iter().find(|x| x.name == *profile).cloned() {
+        args.epsilon_static_value = overrides.epsilon.or(args.epsilon_static_value.take());
+        args.max_relative_static_value = overrides
+            .max_relative
+            .or(args.max_relative_static_value.take());
+        args.max_ulps_static_value = overrides.max_ulps.or(args.max_ulps_static_value.take());
+    }
+    field_with_args
+}
+
+impl AbsDiffEqParser {
+    /// Name of the generated enum such as `StateProfile` for the struct `State`
+    pub fn get_profile_enum_ident(&self) -> syn::Ident {
+        let ident = self.base_type.ident();
+        syn::Ident::new(&format!("{ident}Profile"), ident.span())
+    }
+
+    /// Ensures that every profile of a field is specified on the object.
+    pub fn check_profiles(&self) -> syn::Result<()> {
+        for field_with_args in self.get_all_fields() {
+            for profile in field_with_args.args.profiles.iter() {
+                let known = self
+                    .struct_args
+                    .profiles
+                    .iter()
+                    .any(|x| x.name == profile.name);
+                if !known {
+                    return Err(syn::Error::new(
+                        profile.name.span(),
+                        format!(
+                            "unknown profile `{}`, profiles need to be specified on the object",
+                            profile.name
+                        ),
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Defines the enum which selects one of the profiles given by
+    /// `#[approx(profile(name(...)))]` on the object.
+    pub fn implement_profile_enum(&self) -> proc_macro2::TokenStream {
+        if self.struct_args.profiles.is_empty() {
+            return quote::quote!();
+        }
+        let vis = self.base_type.vis();
+        let enum_ident = self.get_profile_enum_ident();
+        let doc = format!("Tolerance profiles of [{}]", self.base_type.ident());
+        let variants = self.struct_args.profiles.iter().map(|profile| {
+            let variant = get_profile_variant(&profile.name);
+            let doc = format!("The profile `{}`", profile.name);
+            quote::quote!(
+                #[doc = #doc]
+                #variant
+            )
+        });
+        quote::quote!(
+            #[doc = #doc]
+            #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+            #vis enum #enum_ident {
+                #(#variants,)*
+            }
+        )
+    }
+
+    /// Binds the tolerances of a profile which fall back to the defaults of the object.
+    ///
+    /// Tolerances which are not used by the method of the trait are only bound if a field is
+    /// compared with a different method by `#[approx(compare = ...)]`.
+    fn get_profile_tolerances(
+        &self,
+        approx_trait: ApproxTrait,
+        profile: &Profile,
+    ) -> proc_macro2::TokenStream {
+        let trait_rhs = self.get_trait_rhs();
+        let epsilon = match &profile.epsilon {
+            Some(epsilon) => quote::quote!(#epsilon),
+            None => quote::quote!(
+                <Self as #ApproxName::AbsDiffEq #trait_rhs>::default_epsilon()
+            ),
+        };
+        let max_relative = match (&profile.max_relative, approx_trait) {
+            (Some(max_relative), _) => Some(quote::quote!(#max_relative)),
+            (None, ApproxTrait::Relative) => Some(quote::quote!(
+                <Self as #ApproxName::RelativeEq #trait_rhs>::default_max_relative()
+            )),
+            (None, _) => Some(self.get_max_relative_default_value()),
+        }
+        .filter(|_| {
+            approx_trait == ApproxTrait::Relative
+                || self.compares_any_field_with(ApproxTrait::Relative)
+        })
+        .map(|max_relative| {
+            quote::quote!(
+                #[allow(unused_variables)]
+                let max_relative = #max_relative;
+            )
+        });
+        let max_ulps = match (&profile.max_ulps, approx_trait) {
+            (Some(max_ulps), _) => Some(quote::quote!(#max_ulps)),
+            (None, ApproxTrait::Ulps) => Some(quote::quote!(
+                <Self as #ApproxName::UlpsEq #trait_rhs>::default_max_ulps()
+            )),
+            (None, _) => Some(self.get_max_ulps_default_value()),
+        }
+        .filter(|_| {
+            approx_trait == ApproxTrait::Ulps || self.compares_any_field_with(ApproxTrait::Ulps)
+        })
+        .map(|max_ulps| {
+            quote::quote!(
+                #[allow(unused_variables)]
+                let max_ulps = #max_ulps;
+            )
+        });
+        quote::quote!(
+            let epsilon: <Self as #ApproxName::AbsDiffEq #trait_rhs>::Epsilon = #epsilon;
+            #max_relative
+            #max_ulps
+        )
+    }
+
+    /// Generates an inherent method such as `abs_diff_eq_profile` which compares both values
+    /// with the tolerances of the selected profile.
+    pub fn implement_profiles(&self, approx_trait: ApproxTrait) -> proc_macro2::TokenStream {
+        if self.struct_args.profiles.is_empty() {
+            return quote::quote!();
+        }
+        let obj_name = &self.base_type.ident();
+        let (impl_generics, ty_generics, _) = self.base_type.generics().split_for_impl();
+        let where_clause = self.generate_where_clause(approx_trait);
+        let rhs_type = self.get_rhs_type();
+        let special_values = self.get_special_values_helpers_if_used();
+        let enum_ident = self.get_profile_enum_ident();
+
+        let method = match approx_trait {
+            ApproxTrait::Abs => "abs_diff_eq",
+            ApproxTrait::Relative => "relative_eq",
+            ApproxTrait::Ulps => "ulps_eq",
+        };
+        let method_name = syn::Ident::new(&format!("{method}_profile"), obj_name.span());
+        let doc = format!("Compares both values like `{method}` with the tolerances of a profile");
+        let arms = self.struct_args.profiles.iter().map(|profile| {
+            let variant = get_profile_variant(&profile.name);
+            let tolerances = self.get_profile_tolerances(approx_trait, profile);
+            let comparison = self.get_selected_comparison(approx_trait, |field_with_args| {
+                Some(get_profile_field(field_with_args, &profile.name))
+            });
+            let comparison = self.with_computed_properties(approx_trait, comparison);
+            quote::quote!(
+                #enum_ident::#variant => {
+                    #tolerances
+                    #comparison
+                }
+            )
+        });
+
+        quote::quote!(
+            const _: () = {
+                #special_values
+
+                #[automatically_derived]
+                impl #impl_generics #obj_name #ty_generics
+                #where_clause
+                {
+                    #[doc = #doc]
+                    pub fn #method_name(&self, other: &#rhs_type, profile: #enum_ident) -> bool {
+                        match profile {
+                            #(#arms)*
+                        }
+                    }
+                }
+            };
+        )
+    }
+}
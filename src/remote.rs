@@ -0,0 +1,205 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::AbsDiffEqParser;
+
+impl AbsDiffEqParser {
+    /// The remote type given by `#[approx(remote = "...")]` together with the generic arguments
+    /// of the local definition if the path does not specify any.
+    fn get_remote_type(&self, remote: &syn::Path) -> proc_macro2::TokenStream {
+        let (_, ty_generics, _) = self.base_type.generics().split_for_impl();
+        match remote.segments.last().map(|segment| &segment.arguments) {
+            Some(syn::PathArguments::None) => quote::quote!(#remote #ty_generics),
+            _ => quote::quote!(#remote),
+        }
+    }
+
+    /// Converts `self` of the remote type to the local definition.
+    ///
+    /// The conversion is never called but ensures that the fields of the local definition match
+    /// the remote type and are considered to be used.
+    /// Since the patterns are exhaustive, the local definition needs to mirror every field and
+    /// variant of the remote type.
+    fn get_remote_conversion(&self) -> proc_macro2::TokenStream {
+        let obj_name = &self.base_type.ident();
+        let convert = |path: proc_macro2::TokenStream, fields_with_args: &[FieldWithArgs]| {
+            let names = |var: &str| -> Vec<syn::Ident> {
+                (0..fields_with_args.len())
+                    .map(|n| quote::format_ident!("{var}{n}"))
+                    .collect()
+            };
+            let (names1, names2) = (names("x"), names("y"));
+            let (remote, local, fields) =
+                match fields_with_args.first().map(|field| field.ident.is_some()) {
+                    None => (
+                        quote::quote!(Self #path),
+                        quote::quote!(#obj_name #path),
+                        quote::quote!(#obj_name #path),
+                    ),
+                    Some(true) => {
+                        let idents: Vec<_> = fields_with_args.iter().map(|f| &f.ident).collect();
+                        (
+                            quote::quote!(Self #path { #(#idents: #names1),* }),
+                            quote::quote!(#obj_name #path { #(#idents: #names1),* }),
+                            quote::quote!(#obj_name #path { #(#idents: #names2),* }),
+                        )
+                    }
+                    Some(false) => (
+                        quote::quote!(Self #path (#(#names1),*)),
+                        quote::quote!(#obj_name #path (#(#names1),*)),
+                        quote::quote!(#obj_name #path (#(#names2),*)),
+                    ),
+                };
+            // Reading the fields of the local definition once avoids warnings about them
+            quote::quote!(
+                #remote => {
+                    let local = #local;
+                    #[allow(irrefutable_let_patterns)]
+                    if let #fields = &local {
+                        let _ = (#(#names2,)*);
+                    }
+                    local
+                }
+            )
+        };
+        match &self.base_type {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => {
+                let arm = convert(quote::quote!(), fields_with_args);
+                quote::quote!(
+                    match self {
+                        #arm
+                    }
+                )
+            }
+            #[allow(unused)]
+            BaseType::Enum {
+                item_enum,
+                variants_with_args,
+            } => {
+                let arms = variants_with_args.iter().map(|variant_with_args| {
+                    let variant = &variant_with_args.ident;
+                    convert(
+                        quote::quote!(::#variant),
+                        &variant_with_args.fields_with_args,
+                    )
+                });
+                quote::quote!(
+                    match self {
+                        #(#arms)*
+                    }
+                )
+            }
+        }
+    }
+
+    /// Ensures that only the comparison itself is requested for remote types.
+    pub fn check_remote(&self) -> syn::Result<()> {
+        if self.struct_args.remote.is_none() {
+            return Ok(());
+        }
+        for field_with_args in self.get_all_fields() {
+            if let Some(group) = field_with_args.args.groups.first() {
+                return Err(syn::Error::new(
+                    group.span(),
+                    "field groups are not supported for remote types",
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Generates associated functions such as `abs_diff_eq(&a, &b, epsilon)` on the local
+    /// definition which compare two values of the remote type.
+    ///
+    /// The comparison is implemented by a private trait for the remote type such that fields and
+    /// enum variants are accessed by `self` and `Self` like in the derived traits.
+    /// The associated functions can be used with `#[approx(with = ...)]`.
+    pub fn implement_remote(&self, approx_trait: ApproxTrait) -> proc_macro2::TokenStream {
+        let Some(remote) = &self.struct_args.remote else {
+            return quote::quote!();
+        };
+        let remote_type = self.get_remote_type(remote);
+        let obj_name = &self.base_type.ident();
+        let (impl_generics, ty_generics, _) = self.base_type.generics().split_for_impl();
+        let where_clause = self.generate_where_clause(approx_trait);
+        let (epsilon_type, _) = self.get_epsilon_type_and_default_value();
+        let special_values = self.get_special_values_helpers_if_used();
+        let conversion = self.get_remote_conversion();
+        let missing_tolerances = self.get_missing_tolerances(approx_trait);
+        let comparison = self.with_computed_properties(
+            approx_trait,
+            self.get_selected_comparison(approx_trait, |field_with_args| {
+                Some(field_with_args.clone())
+            }),
+        );
+
+        let (method, arguments) = match approx_trait {
+            ApproxTrait::Abs => ("abs_diff_eq", quote::quote!(epsilon)),
+            ApproxTrait::Relative => ("relative_eq", quote::quote!(epsilon, max_relative)),
+            ApproxTrait::Ulps => ("ulps_eq", quote::quote!(epsilon, max_ulps)),
+        };
+        let get_tolerances = |epsilon_type| match approx_trait {
+            ApproxTrait::Abs => quote::quote!(epsilon: #epsilon_type),
+            ApproxTrait::Relative => {
+                quote::quote!(epsilon: #epsilon_type, max_relative: #epsilon_type)
+            }
+            ApproxTrait::Ulps => quote::quote!(epsilon: #epsilon_type, max_ulps: u32),
+        };
+        // The epsilon type may depend on type parameters which are unknown to the private trait
+        let trait_tolerances = get_tolerances(quote::quote!(Self::Epsilon));
+        let tolerances = get_tolerances(epsilon_type.clone());
+        let method_name = syn::Ident::new(method, obj_name.span());
+        let private_method = quote::format_ident!("__approx_{method}");
+        let doc = format!(
+            "Compares two values of the remote type `{}` like `{method}`",
+            quote::quote!(#remote).to_string().replace(' ', "")
+        );
+
+        quote::quote!(
+            const _: () = {
+                #special_values
+
+                trait __ApproxRemote {
+                    type Epsilon;
+                    type Local;
+                    fn #private_method(&self, other: &Self, #trait_tolerances) -> bool;
+                    #[allow(dead_code)]
+                    fn __approx_local(self) -> Self::Local;
+                }
+
+                impl #impl_generics __ApproxRemote for #remote_type
+                #where_clause
+                {
+                    type Epsilon = #epsilon_type;
+                    type Local = #obj_name #ty_generics;
+
+                    fn __approx_local(self) -> Self::Local {
+                        #conversion
+                    }
+
+                    fn #private_method(&self, other: &Self, #trait_tolerances) -> bool {
+                        #missing_tolerances
+                        #comparison
+                    }
+                }
+
+                #[automatically_derived]
+                impl #impl_generics #obj_name #ty_generics
+                #where_clause
+                {
+                    #[doc = #doc]
+                    pub fn #method_name(
+                        a: &#remote_type,
+                        b: &#remote_type,
+                        #tolerances
+                    ) -> bool {
+                        __ApproxRemote::#private_method(a, b, #arguments)
+                    }
+                }
+            };
+        )
+    }
+}
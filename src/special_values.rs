@@ -0,0 +1,78 @@
+use crate::args_parsing::*;
+use crate::AbsDiffEqParser;
+
+/// Items which are used to compare special floating point values.
+///
+/// Only `f32` and `f64` can be `NaN` or infinite.
+/// By autoref specialization, all other types fall back to an implementation which never
+/// considers them equal such that the regular comparison decides.
+pub fn get_special_values_helpers() -> proc_macro2::TokenStream {
+    quote::quote!(
+        struct __ApproxSpecial<'a, T: ?Sized>(&'a T, &'a T);
+        trait __ApproxSpecialEq {
+            fn __approx_special_eq(&self, nan_equal: bool, inf_equal: bool) -> bool;
+        }
+        impl __ApproxSpecialEq for __ApproxSpecial<'_, f32> {
+            fn __approx_special_eq(&self, nan_equal: bool, inf_equal: bool) -> bool {
+                let (a, b) = (*self.0, *self.1);
+                (nan_equal && a.is_nan() && b.is_nan()) || (inf_equal && a.is_infinite() && a == b)
+            }
+        }
+        impl __ApproxSpecialEq for __ApproxSpecial<'_, f64> {
+            fn __approx_special_eq(&self, nan_equal: bool, inf_equal: bool) -> bool {
+                let (a, b) = (*self.0, *self.1);
+                (nan_equal && a.is_nan() && b.is_nan()) || (inf_equal && a.is_infinite() && a == b)
+            }
+        }
+        impl __ApproxSpecialEq for __ApproxSpecial<'_, &f32> {
+            fn __approx_special_eq(&self, nan_equal: bool, inf_equal: bool) -> bool {
+                __ApproxSpecial(*self.0, *self.1).__approx_special_eq(nan_equal, inf_equal)
+            }
+        }
+        impl __ApproxSpecialEq for __ApproxSpecial<'_, &f64> {
+            fn __approx_special_eq(&self, nan_equal: bool, inf_equal: bool) -> bool {
+                __ApproxSpecial(*self.0, *self.1).__approx_special_eq(nan_equal, inf_equal)
+            }
+        }
+        trait __ApproxSpecialFallback {
+            fn __approx_special_eq(&self, nan_equal: bool, inf_equal: bool) -> bool;
+        }
+        impl<T: ?Sized> __ApproxSpecialFallback for &__ApproxSpecial<'_, T> {
+            fn __approx_special_eq(&self, _: bool, _: bool) -> bool {
+                false
+            }
+        }
+    )
+}
+
+/// Extends the comparison of the references `a` and `b` such that `NaN` or infinite values are
+/// considered equal if specified by `#[approx(nan_equal)]` or `#[approx(inf_equal)]`.
+pub fn with_special_values(
+    args: &FieldArgs,
+    a: proc_macro2::TokenStream,
+    b: proc_macro2::TokenStream,
+    check: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let nan_equal = args.nan_equal.unwrap_or(false);
+    let inf_equal = args.inf_equal.unwrap_or(false);
+    if !nan_equal && !inf_equal {
+        return check;
+    }
+    quote::quote!(
+        ((&__ApproxSpecial(#a, #b)).__approx_special_eq(#nan_equal, #inf_equal) || #check)
+    )
+}
+
+impl AbsDiffEqParser {
+    /// Determines if the helpers of [get_special_values_helpers] need to be generated
+    pub fn get_special_values_helpers_if_used(&self) -> proc_macro2::TokenStream {
+        let used = self.get_all_fields().into_iter().any(|field_with_args| {
+            field_with_args.args.nan_equal.unwrap_or(false)
+                || field_with_args.args.inf_equal.unwrap_or(false)
+        });
+        match used {
+            true => get_special_values_helpers(),
+            false => quote::quote!(),
+        }
+    }
+}
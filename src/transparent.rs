@@ -0,0 +1,311 @@
+use crate::base_types::*;
+use crate::AbsDiffEqParser;
+
+impl AbsDiffEqParser {
+    /// The member and type of the single field which is not skipped
+    fn get_transparent_field(&self) -> Option<(proc_macro2::TokenStream, &syn::Type)> {
+        let BaseType::Struct {
+            fields_with_args, ..
+        } = &self.base_type
+        else {
+            return None;
+        };
+        let (n, field_with_args) = fields_with_args
+            .iter()
+            .enumerate()
+            .find(|(_, field_with_args)| !field_with_args.args.skip.unwrap_or(false))?;
+        let member = match &field_with_args.ident {
+            Some(ident) => quote::quote!(#ident),
+            None => {
+                let index = syn::Index::from(n);
+                quote::quote!(#index)
+            }
+        };
+        Some((member, &field_with_args.ty))
+    }
+
+    /// Ensures that `#[approx(transparent)]` is used for a struct with a single compared field.
+    pub fn check_transparent(&self) -> syn::Result<()> {
+        if !self.struct_args.transparent.unwrap_or(false) {
+            return Ok(());
+        }
+        use syn::spanned::Spanned;
+        match &self.base_type {
+            BaseType::Enum { item_enum, .. } => Err(syn::Error::new(
+                item_enum.ident.span(),
+                "transparent is only supported for structs",
+            )),
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => {
+                let mut compared = fields_with_args
+                    .iter()
+                    .zip(item_struct.fields.iter())
+                    .filter(|(field_with_args, _)| !field_with_args.args.skip.unwrap_or(false));
+                match (compared.next(), compared.next()) {
+                    // The wrapped field is compared with its own traits so its arguments would
+                    // be silently ignored
+                    (Some((_, field)), None) => {
+                        if field
+                            .attrs
+                            .iter()
+                            .any(|attr| attr.path().is_ident("approx"))
+                        {
+                            return Err(syn::Error::new(
+                                field.span(),
+                                "the field wrapped by transparent can not have approx attributes",
+                            ));
+                        }
+                        Ok(())
+                    }
+                    (_, Some((second, _))) => Err(syn::Error::new(
+                        second.ty.span(),
+                        "transparent requires exactly one field which is not skipped",
+                    )),
+                    (None, None) => Err(syn::Error::new(
+                        item_struct.ident.span(),
+                        "transparent requires exactly one field which is not skipped",
+                    )),
+                }
+            }
+        }
+    }
+
+    /// Ensures that no field or variant of a type compared by `#[approx(via = ...)]` has approx
+    /// attributes since only the converted values are compared.
+    pub fn check_via(&self) -> syn::Result<()> {
+        if self.struct_args.via.is_none() {
+            return Ok(());
+        }
+        let attrs: Vec<&syn::Attribute> = match &self.base_type {
+            BaseType::Struct { item_struct, .. } => item_struct
+                .fields
+                .iter()
+                .flat_map(|field| field.attrs.iter())
+                .collect(),
+            BaseType::Enum { item_enum, .. } => item_enum
+                .variants
+                .iter()
+                .flat_map(|variant| {
+                    variant
+                        .attrs
+                        .iter()
+                        .chain(variant.fields.iter().flat_map(|field| field.attrs.iter()))
+                })
+                .collect(),
+        };
+        match attrs.iter().find(|attr| attr.path().is_ident("approx")) {
+            Some(attr) => {
+                use syn::spanned::Spanned;
+                Err(syn::Error::new(
+                    attr.span(),
+                    "fields of a type compared via a conversion can not have approx attributes",
+                ))
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Determines if the traits are forwarded to another type by `#[approx(transparent)]` or
+    /// `#[approx(via = ...)]`.
+    pub fn uses_forwarding(&self) -> bool {
+        self.struct_args.transparent.unwrap_or(false) || self.struct_args.via.is_some()
+    }
+
+    /// The type to which the traits are forwarded together with the references to the values of
+    /// `self` and `other` which are compared instead.
+    fn get_forwarding_target(
+        &self,
+    ) -> Option<(
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+        proc_macro2::TokenStream,
+    )> {
+        if let Some(via) = &self.struct_args.via {
+            let convert = match &self.struct_args.convert {
+                Some(convert) => quote::quote!(#convert),
+                None => quote::quote!(<#via as From<&Self>>::from),
+            };
+            return Some((
+                quote::quote!(#via),
+                quote::quote!(&(#convert)(self)),
+                quote::quote!(&(#convert)(other)),
+            ));
+        }
+        let (member, inner) = self.get_transparent_field()?;
+        Some((
+            quote::quote!(#inner),
+            quote::quote!(&self.#member),
+            quote::quote!(&other.#member),
+        ))
+    }
+
+    /// Implements the trait by forwarding to the single compared field given by
+    /// `#[approx(transparent)]` or to the conversion into the type given by
+    /// `#[approx(via = ...)]`.
+    ///
+    /// The epsilon type and default tolerances are taken from the target type and the only
+    /// inferred bound requires it to implement the trait.
+    pub fn implement_forwarding(&self, approx_trait: ApproxTrait) -> proc_macro2::TokenStream {
+        let Some((inner, a, b)) = self.get_forwarding_target() else {
+            return quote::quote!();
+        };
+        let obj_name = &self.base_type.ident();
+        let (impl_generics, ty_generics, where_clause) = self.base_type.generics().split_for_impl();
+        let mut predicates: Vec<proc_macro2::TokenStream> = where_clause
+            .map(|clause| {
+                clause
+                    .predicates
+                    .iter()
+                    .map(|x| quote::quote!(#x))
+                    .collect()
+            })
+            .unwrap_or_default();
+        match &self.struct_args.bound {
+            Some(bound) => predicates.extend(bound.iter().map(|x| quote::quote!(#x))),
+            None => predicates.push(quote::quote!(#inner: #approx_trait)),
+        }
+
+        let items = match approx_trait {
+            ApproxTrait::Abs => quote::quote!(
+                type Epsilon = <#inner as #ApproxName::AbsDiffEq>::Epsilon;
+
+                fn default_epsilon() -> Self::Epsilon {
+                    <#inner as #ApproxName::AbsDiffEq>::default_epsilon()
+                }
+
+                fn abs_diff_eq(&self, other: &Self, epsilon: Self::Epsilon) -> bool {
+                    <#inner as #ApproxName::AbsDiffEq>::abs_diff_eq(#a, #b, epsilon)
+                }
+            ),
+            ApproxTrait::Relative => quote::quote!(
+                fn default_max_relative() -> Self::Epsilon {
+                    <#inner as #ApproxName::RelativeEq>::default_max_relative()
+                }
+
+                fn relative_eq(
+                    &self,
+                    other: &Self,
+                    epsilon: Self::Epsilon,
+                    max_relative: Self::Epsilon,
+                ) -> bool {
+                    <#inner as #ApproxName::RelativeEq>::relative_eq(#a, #b, epsilon, max_relative)
+                }
+            ),
+            ApproxTrait::Ulps => quote::quote!(
+                fn default_max_ulps() -> u32 {
+                    <#inner as #ApproxName::UlpsEq>::default_max_ulps()
+                }
+
+                fn ulps_eq(&self, other: &Self, epsilon: Self::Epsilon, max_ulps: u32) -> bool {
+                    <#inner as #ApproxName::UlpsEq>::ulps_eq(#a, #b, epsilon, max_ulps)
+                }
+            ),
+        };
+
+        quote::quote!(
+            #[automatically_derived]
+            impl #impl_generics #approx_trait for #obj_name #ty_generics
+            where #(#predicates),*
+            {
+                #items
+            }
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The message of the error returned for the given item
+    fn parser_error(item: proc_macro2::TokenStream) -> String {
+        match syn::parse2::<AbsDiffEqParser>(item) {
+            Ok(_) => String::from("no error"),
+            Err(error) => error.to_string(),
+        }
+    }
+
+    #[test]
+    fn wrapped_field_attributes() {
+        let message = "the field wrapped by transparent can not have approx attributes";
+        assert_eq!(
+            parser_error(quote::quote!(
+                #[approx(transparent)]
+                struct Meters(#[approx(epsilon = 0.1)] f64);
+            )),
+            message
+        );
+        assert_eq!(
+            parser_error(quote::quote!(
+                #[approx(transparent)]
+                struct Labeled {
+                    #[approx(skip)]
+                    label: String,
+                    #[approx(map = |x| Some(*x))]
+                    value: f64,
+                }
+            )),
+            message
+        );
+        assert_eq!(
+            parser_error(quote::quote!(
+                #[approx(transparent)]
+                struct Labeled {
+                    value: f64,
+                    #[approx(skip)]
+                    label: String,
+                }
+            )),
+            "no error"
+        );
+    }
+
+    #[test]
+    fn via_field_attributes() {
+        let message = "fields of a type compared via a conversion can not have approx attributes";
+        assert_eq!(
+            parser_error(quote::quote!(
+                #[approx(via = Canonical)]
+                struct Angle {
+                    #[approx(epsilon = 0.1)]
+                    radians: f64,
+                }
+            )),
+            message
+        );
+        assert_eq!(
+            parser_error(quote::quote!(
+                #[approx(via = Canonical)]
+                struct Labeled {
+                    value: f64,
+                    #[approx(skip)]
+                    label: String,
+                }
+            )),
+            message
+        );
+        assert_eq!(
+            parser_error(quote::quote!(
+                #[approx(via = Canonical)]
+                enum Shape {
+                    #[approx(equal)]
+                    Circle(f64),
+                    Square(f64),
+                }
+            )),
+            message
+        );
+        assert_eq!(
+            parser_error(quote::quote!(
+                #[approx(via = Canonical, convert = canonicalize)]
+                enum Shape {
+                    Circle(f64),
+                    Square { side: f64 },
+                }
+            )),
+            "no error"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use crate::args_parsing::*;
+use crate::base_types::*;
+use crate::AbsDiffEqParser;
+
+impl AbsDiffEqParser {
+    fn get_ulps_eq_struct_fields(
+        &self,
+        fields_with_args: &[FieldWithArgs],
+    ) -> Vec<proc_macro2::TokenStream> {
+        fields_with_args
+            .iter()
+            .enumerate()
+            .filter_map(|(n, field_with_args)| {
+                self.get_single_field_check(ApproxTrait::Ulps, n, field_with_args, None)
+            })
+            .collect()
+    }
+
+    fn get_ulps_eq_variants(
+        &self,
+        variants_with_args: &[EnumVariant],
+    ) -> Vec<proc_macro2::TokenStream> {
+        variants_with_args
+            .iter()
+            .map(|variant_with_args| {
+                let (pattern1, pattern2, idents) = variant_with_args.get_match_patterns();
+                let comps: Vec<_> = variant_with_args
+                    .fields_with_args
+                    .iter()
+                    .zip(idents)
+                    .filter_map(|(field, idents)| {
+                        self.get_single_field_check(ApproxTrait::Ulps, 0, field, Some(idents))
+                    })
+                    .collect();
+                quote::quote!(
+                    (#pattern1, #pattern2) => #(#comps &&)* true,
+                )
+            })
+            .collect()
+    }
+
+    pub fn implement_derive_ulps_eq(&self) -> proc_macro2::TokenStream {
+        let obj_name = &self.base_type.ident();
+        let max_ulps_default_value = self.get_max_ulps_default_value();
+
+        let (impl_generics, ty_generics, _) = self.base_type.generics().split_for_impl();
+        let rhs_type = self.get_rhs_type();
+        let trait_rhs = self.get_trait_rhs();
+        let where_clause = self.generate_where_clause(ApproxTrait::Ulps);
+        let special_values = self.get_special_values_helpers_if_used();
+        let missing_tolerances = self.get_missing_tolerances(ApproxTrait::Ulps);
+
+        match &self.base_type {
+            #[allow(unused)]
+            BaseType::Struct {
+                item_struct,
+                fields_with_args,
+            } => {
+                let fields = self.get_ulps_eq_struct_fields(fields_with_args);
+                let check = self.with_computed_properties(
+                    ApproxTrait::Ulps,
+                    quote::quote!(#(#fields &&)* true),
+                );
+
+                quote::quote!(
+                    const _ : () = {
+                        #special_values
+
+                        #[automatically_derived]
+                        impl #impl_generics #ApproxName::UlpsEq #trait_rhs for #obj_name #ty_generics
+                        #where_clause
+                        {
+                            fn default_max_ulps() -> u32 {
+                                #max_ulps_default_value
+                            }
+
+                            fn ulps_eq(
+                                &self,
+                                other: &#rhs_type,
+                                epsilon: Self::Epsilon,
+                                max_ulps: u32
+                            ) -> bool {
+                                #missing_tolerances
+                                #check
+                            }
+                        }
+                    };
+                )
+            }
+            #[allow(unused)]
+            BaseType::Enum {
+                item_enum,
+                variants_with_args,
+            } => {
+                let variants = self.get_ulps_eq_variants(variants_with_args);
+                let check = self.with_computed_properties(
+                    ApproxTrait::Ulps,
+                    quote::quote!(match (self, other) {
+                        #(#variants)*
+                        _ => false,
+                    }),
+                );
+                quote::quote!(
+                    const _: () = {
+                        #special_values
+
+                        #[automatically_derived]
+                        impl #impl_generics #ApproxName::UlpsEq for #obj_name #ty_generics
+                        #where_clause
+                        {
+                            fn default_max_ulps() -> u32 {
+                                #max_ulps_default_value
+                            }
+
+                            fn ulps_eq(
+                                &self,
+                                other: &Self,
+                                epsilon: Self::Epsilon,
+                                max_ulps: u32
+                            ) -> bool {
+                                #missing_tolerances
+                                #check
+                            }
+                        }
+                    };
+                )
+            }
+        }
+    }
+}
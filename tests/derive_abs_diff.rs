@@ -86,6 +86,35 @@ fn derive_abs_diff_eq_static_epsilon() {
     approx::assert_abs_diff_eq!(ms1, ms2);
 }
 
+#[test]
+fn derive_abs_diff_eq_multiple_args() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f32, default_epsilon = 0.01)]
+    struct MyStructMultiple {
+        v1: f32,
+        #[approx(cast_field, static_epsilon = 0.002, epsilon_map = |x: f32| x / 2.0)]
+        v2: f64,
+    }
+    let ms1 = MyStructMultiple { v1: 1.0, v2: 1.0 };
+    let ms2 = MyStructMultiple {
+        v1: 1.0,
+        v2: 1.0009,
+    };
+    let ms3 = MyStructMultiple {
+        v1: 1.0,
+        v2: 1.0011,
+    };
+    approx::assert_abs_diff_eq!(ms1, ms2);
+    approx::assert_abs_diff_ne!(ms1, ms3);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    enum Reading {
+        #[approx(cast_value, static_epsilon = 1)]
+        Discrete(u8, u8),
+    }
+    approx::assert_abs_diff_eq!(Reading::Discrete(1, 2), Reading::Discrete(2, 3));
+}
+
 #[test]
 fn derive_abs_diff_eq_skip() {
     #[derive(AbsDiffEq, PartialEq, Debug)]
@@ -143,6 +172,68 @@ fn derive_abs_diff_eq_generics_tuple() {
     approx::assert_abs_diff_ne!(p1, p2);
 }
 
+#[test]
+fn derive_abs_diff_eq_generics_inferred_bounds() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Samples<F, G> {
+        offset: F,
+        scale: G,
+        #[approx(into_iter)]
+        values: Vec<F>,
+        #[approx(equal)]
+        label: G,
+    }
+    let s1 = Samples {
+        offset: 1.0,
+        scale: 2.0,
+        values: vec![1.0, 2.0],
+        label: 3.0,
+    };
+    let s2 = Samples {
+        offset: 1.0,
+        scale: 2.0,
+        values: vec![1.0, 2.1],
+        label: 3.0,
+    };
+    approx::assert_abs_diff_eq!(s1, s2, epsilon = 0.2);
+    approx::assert_abs_diff_ne!(s1, s2, epsilon = 0.05);
+}
+
+#[test]
+fn derive_abs_diff_eq_generics_bound() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64)]
+    struct Wrapper<T> {
+        #[approx(map = |x: &T| Some((*x).into()))]
+        #[approx(bound = "T: Into<f64> + Copy")]
+        value: T,
+    }
+    approx::assert_abs_diff_eq!(
+        Wrapper { value: 1_f32 },
+        Wrapper { value: 1.5_f32 },
+        epsilon = 0.6
+    );
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64)]
+    #[approx(bound = "T: approx::AbsDiffEq<Epsilon = f64>")]
+    struct Pair<T> {
+        first: T,
+        second: T,
+    }
+    approx::assert_abs_diff_eq!(
+        Pair {
+            first: 1.0,
+            second: 2.0
+        },
+        Pair {
+            first: 1.0,
+            second: 2.1
+        },
+        epsilon = 0.2
+    );
+}
+
 #[test]
 fn derive_abs_diff_option() {
     #[derive(AbsDiffEq, PartialEq, Debug)]
@@ -311,19 +402,6 @@ fn derive_abs_diff_mapping_function() {
     approx::assert_abs_diff_eq!(d1, d2, epsilon = 0);
 }
 
-#[test]
-fn derive_abs_diff_equal_higher_priority_than_mapping() {
-    #[derive(AbsDiffEq, PartialEq, Debug)]
-    struct Length {
-        #[approx(equal)]
-        #[approx(map = |x: &f32| Some(2.0*x))]
-        meters: f32,
-    }
-    let l1 = Length { meters: 3.0 };
-    let l2 = Length { meters: 3.0001 };
-    approx::assert_abs_diff_ne!(l1, l2, epsilon = 0.001);
-}
-
 #[test]
 fn derive_abs_diff_equal_enum() {
     #[derive(AbsDiffEq, PartialEq, Debug)]
@@ -554,3 +632,1378 @@ fn epsilon_mapping() {
     approx::assert_abs_diff_ne!(a1, a2);
     approx::assert_abs_diff_eq!(a1, a2, epsilon = 1.1);
 }
+
+#[test]
+fn derive_abs_diff_eq_rhs() {
+    #[derive(Debug)]
+    struct Reference {
+        temperature: f64,
+        pressure: f64,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(rhs = Reference)]
+    struct Measurement {
+        temperature: f64,
+        #[approx(cast_field)]
+        pressure: f32,
+        #[approx(skip)]
+        id: usize,
+    }
+
+    impl PartialEq<Reference> for Measurement {
+        fn eq(&self, other: &Reference) -> bool {
+            self.temperature == other.temperature && self.pressure as f64 == other.pressure
+        }
+    }
+
+    let m = Measurement {
+        temperature: 293.1,
+        pressure: 1.013,
+        id: 3,
+    };
+    let r = Reference {
+        temperature: 293.15,
+        pressure: 1.0,
+    };
+    approx::assert_abs_diff_ne!(m, r);
+    approx::assert_abs_diff_eq!(m, r, epsilon = 0.1);
+}
+
+#[test]
+fn derive_abs_diff_eq_rhs_tuple() {
+    #[derive(Debug)]
+    struct Reference(f32, f32);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(rhs = Reference)]
+    struct Position(f32, f32);
+
+    impl PartialEq<Reference> for Position {
+        fn eq(&self, other: &Reference) -> bool {
+            self.0 == other.0 && self.1 == other.1
+        }
+    }
+
+    approx::assert_abs_diff_eq!(Position(1.0, 2.0), Reference(1.0, 2.1), epsilon = 0.2);
+    approx::assert_abs_diff_ne!(Position(1.0, 2.0), Reference(1.0, 2.1), epsilon = 0.01);
+}
+
+#[test]
+fn derive_abs_diff_eq_rhs_generic() {
+    #[derive(Debug)]
+    struct Reference<T, U> {
+        value: T,
+        weight: U,
+    }
+
+    // Only the type which determines the epsilon type receives an inferred bound
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(rhs = Reference<T, U>)]
+    struct Measurement<T, U> {
+        value: T,
+        #[approx(bound = "U: approx::AbsDiffEq<Epsilon = T::Epsilon>")]
+        weight: U,
+    }
+
+    impl<T: PartialEq, U: PartialEq> PartialEq<Reference<T, U>> for Measurement<T, U> {
+        fn eq(&self, other: &Reference<T, U>) -> bool {
+            self.value == other.value && self.weight == other.weight
+        }
+    }
+
+    let m = Measurement {
+        value: 1.0,
+        weight: 2.0,
+    };
+    let r = Reference {
+        value: 1.0,
+        weight: 2.1,
+    };
+    approx::assert_abs_diff_ne!(m, r);
+    approx::assert_abs_diff_eq!(m, r, epsilon = 0.2);
+}
+
+#[test]
+fn derive_abs_diff_eq_epsilon_struct() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_struct)]
+    struct State {
+        position: f64,
+        #[approx(static_epsilon = 0.5)]
+        velocity: f32,
+        #[approx(equal)]
+        id: usize,
+    }
+
+    let s1 = State {
+        position: 1.0,
+        velocity: 3.0,
+        id: 1,
+    };
+    let s2 = State {
+        position: 1.2,
+        velocity: 3.4,
+        id: 1,
+    };
+
+    let default = StateEpsilon::default();
+    assert_eq!(default.position, f64::EPSILON);
+    assert_eq!(default.velocity, 0.5);
+
+    approx::assert_abs_diff_ne!(s1, s2);
+    approx::assert_abs_diff_eq!(
+        s1,
+        s2,
+        epsilon = StateEpsilon {
+            position: 0.25,
+            velocity: 0.5,
+        }
+    );
+    approx::assert_abs_diff_ne!(
+        s1,
+        s2,
+        epsilon = StateEpsilon {
+            position: 0.25,
+            velocity: 0.1,
+        }
+    );
+}
+
+#[test]
+fn derive_abs_diff_eq_epsilon_struct_nested() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_struct)]
+    struct Point {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_struct, default_epsilon = 0.1)]
+    struct Player(
+        f64,
+        #[approx(skip)] usize,
+        #[approx(static_epsilon = PointEpsilon::default())] Point,
+    );
+
+    let p1 = Player(1.0, 3, Point { x: 0.0, y: 1.0 });
+    let p2 = Player(1.05, 4, Point { x: 0.1, y: 1.0 });
+
+    let default = PlayerEpsilon::default();
+    assert_eq!(default.0, 0.1);
+    assert_eq!(default.1.x, f32::EPSILON);
+
+    approx::assert_abs_diff_ne!(p1, p2);
+    approx::assert_abs_diff_eq!(
+        p1,
+        p2,
+        epsilon = PlayerEpsilon(0.1, PointEpsilon { x: 0.2, y: 0.0 })
+    );
+}
+
+#[test]
+fn derive_abs_diff_eq_epsilon_struct_nested_default() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_struct, default_epsilon = 0.5)]
+    struct Velocity {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_struct, default_epsilon = 0.1)]
+    struct Body {
+        mass: f64,
+        velocity: Velocity,
+    }
+
+    let default = BodyEpsilon::default();
+    assert_eq!(default.mass, 0.1);
+    assert_eq!(default.velocity.x, 0.5);
+    assert_eq!(default.velocity.y, 0.5);
+
+    let b1 = Body {
+        mass: 1.0,
+        velocity: Velocity { x: 0.0, y: 1.0 },
+    };
+    let b2 = Body {
+        mass: 1.05,
+        velocity: Velocity { x: 0.3, y: 1.0 },
+    };
+    approx::assert_abs_diff_eq!(b1, b2);
+}
+
+#[test]
+fn derive_abs_diff_eq_mismatches() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct Particle {
+        position: Point,
+        #[approx(equal)]
+        id: usize,
+        #[approx(into_iter)]
+        history: Vec<Point>,
+        #[approx(map = |x: &Option<f64>| *x)]
+        charge: Option<f64>,
+        #[approx(cast_field)]
+        mass: f32,
+    }
+
+    let p1 = Particle {
+        position: Point { x: 1.0, y: 2.0 },
+        id: 1,
+        history: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }],
+        charge: Some(1.0),
+        mass: 2.0,
+    };
+    let p2 = Particle {
+        position: Point { x: 1.0, y: 2.5 },
+        id: 2,
+        history: vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.5, y: 1.0 }],
+        charge: None,
+        mass: 3.0,
+    };
+    assert!(p1.approx_mismatches(&p1, 0.1).is_empty());
+
+    let mismatches = p1.approx_mismatches(&p2, 0.1);
+    let paths: Vec<_> = mismatches.iter().map(|m| m.path.as_str()).collect();
+    assert_eq!(
+        paths,
+        vec!["position.y", "id", "history[1].x", "charge", "mass"]
+    );
+    // Nested entries report the mode of the innermost comparison
+    let modes: Vec<_> = mismatches.iter().map(|m| m.mode).collect();
+    assert_eq!(modes, vec!["approx", "equal", "approx", "map", "cast"]);
+    assert_eq!(mismatches[0].left, "2.0");
+    assert_eq!(mismatches[0].right, "2.5");
+    assert_eq!(mismatches[0].epsilon.as_deref(), Some("0.1"));
+    assert_eq!(mismatches[0].max_relative, None);
+    assert_eq!(mismatches[1].epsilon, None);
+    assert_eq!(mismatches[3].right, "None");
+
+    let with_history = |history: Vec<Point>| Particle {
+        position: Point { x: 1.0, y: 2.0 },
+        id: 1,
+        history,
+        charge: Some(1.0),
+        mass: 2.0,
+    };
+    let p3 = with_history(vec![Point { x: 0.0, y: 0.0 }, Point { x: 1.0, y: 1.0 }]);
+    let p4 = with_history(vec![Point { x: 0.0, y: 0.0 }]);
+    let mismatches = p3.approx_mismatches(&p4, 0.1);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "history");
+    assert_eq!(mismatches[0].left, "2 elements");
+    assert_eq!(mismatches[0].right, "1 elements");
+}
+
+#[test]
+fn derive_abs_diff_eq_mismatches_enum() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    enum Shape {
+        Circle { radius: f32 },
+        Rectangle(f32, #[approx(skip)] usize, f32),
+    }
+
+    let s1 = Shape::Rectangle(1.0, 0, 2.0);
+    let s2 = Shape::Rectangle(1.0, 1, 3.0);
+    let mismatches = s1.approx_mismatches(&s2, 0.1);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "Rectangle.2");
+
+    let s3 = Shape::Circle { radius: 1.0 };
+    let mismatches = s1.approx_mismatches(&s3, 0.1);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "");
+    assert_eq!(mismatches[0].mode, "variant");
+    assert_eq!(mismatches[0].left, "Rectangle");
+    assert_eq!(mismatches[0].right, "Circle");
+
+    #[derive(AbsDiffEq, PartialEq)]
+    #[approx(mismatches)]
+    struct Wrapper {
+        value: f32,
+        shape: Shape,
+    }
+    let w1 = Wrapper {
+        shape: s1,
+        value: 1.0,
+    };
+    let w2 = Wrapper {
+        shape: s2,
+        value: 2.0,
+    };
+    let paths: Vec<_> = w1
+        .approx_mismatches(&w2, 0.1)
+        .into_iter()
+        .map(|m| m.path)
+        .collect();
+    assert_eq!(paths, vec!["value", "shape.Rectangle.2"]);
+}
+
+#[test]
+fn derive_abs_diff_eq_mismatches_names() {
+    // The report is opt-in such that existing types of the same name are not affected
+    #[derive(Debug)]
+    #[allow(unused)]
+    struct ReadingMismatch(&'static str);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Reading {
+        value: f32,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches = SensorDeviation)]
+    struct Sensor {
+        reading: f32,
+    }
+
+    approx::assert_abs_diff_eq!(
+        Reading { value: 1.0 },
+        Reading { value: 1.05 },
+        epsilon = 0.1
+    );
+    let mismatches: Vec<SensorDeviation> =
+        Sensor { reading: 1.0 }.approx_mismatches(&Sensor { reading: 2.0 }, 0.1);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "reading");
+}
+
+#[test]
+fn derive_abs_diff_eq_mismatches_generic() {
+    #[derive(AbsDiffEq, PartialEq)]
+    #[approx(mismatches)]
+    struct Pair<T> {
+        first: T,
+        second: T,
+    }
+
+    let p1 = Pair {
+        first: 1.0,
+        second: 2.0,
+    };
+    let p2 = Pair {
+        first: 1.0,
+        second: 3.0,
+    };
+    let mismatches = p1.approx_mismatches(&p2, 0.1);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "second");
+    // Generic values without a Debug bound can not be formatted
+    assert_eq!(mismatches[0].left, "..");
+}
+
+#[test]
+fn derive_abs_diff_eq_assert_fields() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Point {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Line {
+        start: Point,
+        end: Point,
+    }
+
+    let l1 = Line {
+        start: Point { x: 0.0, y: 0.0 },
+        end: Point { x: 1.0, y: 1.0 },
+    };
+    let l2 = Line {
+        start: Point { x: 0.0, y: 0.0 },
+        end: Point { x: 1.0, y: 1.05 },
+    };
+    assert_fields_abs_diff_eq!(l1, l2, epsilon = 0.1);
+    assert_fields_abs_diff_eq!(&l1, &l1);
+
+    let result = std::panic::catch_unwind(|| assert_fields_abs_diff_eq!(l1, l2));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.starts_with("assertion failed: `assert_fields_abs_diff_eq!(l1, l2)`"));
+    let rows: Vec<_> = message.lines().skip(2).collect();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[0].starts_with("path  | left | right | |diff|"));
+    assert!(rows[2].starts_with("end.y | 1.0  | 1.05  | 0.050000000000000044"));
+}
+
+#[test]
+fn derive_abs_diff_eq_assert_fields_without_report() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Speed(f64);
+
+    assert_fields_abs_diff_eq!(Speed(1.0), Speed(1.05), epsilon = 0.1);
+
+    let result = std::panic::catch_unwind(|| assert_fields_abs_diff_eq!(Speed(1.0), Speed(2.0)));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    let rows: Vec<_> = message.lines().skip(2).collect();
+    assert_eq!(rows.len(), 3);
+    assert!(rows[2].starts_with("(value) | Speed(1.0) | Speed(2.0) | -"));
+}
+
+#[test]
+#[should_panic(expected = "Rectangle.1")]
+fn derive_abs_diff_eq_assert_fields_enum() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    enum Shape {
+        Rectangle(f32, f32),
+    }
+
+    assert_fields_abs_diff_eq!(
+        Shape::Rectangle(1.0, 2.0),
+        Shape::Rectangle(1.0, 3.0),
+        epsilon = 0.5,
+    );
+}
+
+#[test]
+fn derive_abs_diff_eq_map_keys() {
+    use std::collections::{BTreeMap, HashMap};
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Prices {
+        #[approx(map_keys)]
+        fruits: HashMap<String, f64>,
+        #[approx(map_keys, static_epsilon = 0.5)]
+        vegetables: BTreeMap<&'static str, f64>,
+    }
+
+    let p1 = Prices {
+        fruits: HashMap::from([("apple".into(), 1.0), ("pear".into(), 2.0)]),
+        vegetables: BTreeMap::from([("carrot", 0.5), ("potato", 0.2)]),
+    };
+    let p2 = Prices {
+        fruits: HashMap::from([("pear".into(), 2.05), ("apple".into(), 0.95)]),
+        vegetables: BTreeMap::from([("carrot", 0.9), ("potato", 0.3)]),
+    };
+    approx::assert_abs_diff_eq!(p1, p2, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(p1, p2, epsilon = 0.01);
+
+    let p3 = Prices {
+        fruits: HashMap::from([("apple".into(), 1.0), ("cherry".into(), 2.0)]),
+        vegetables: BTreeMap::from([("carrot", 0.5)]),
+    };
+    approx::assert_abs_diff_ne!(p1, p3, epsilon = 10.0);
+    let mismatches = p1.approx_mismatches(&p3, 10.0);
+    let paths: Vec<_> = mismatches.iter().map(|m| m.path.as_str()).collect();
+    assert_eq!(mismatches.len(), 3);
+    assert!(paths.contains(&"fruits[\"pear\"]"));
+    assert!(paths.contains(&"fruits[\"cherry\"]"));
+    assert!(paths.contains(&"vegetables[\"potato\"]"));
+    assert!(mismatches.iter().all(|m| m.mode == "keys"));
+
+    let mismatches = p1.approx_mismatches(&p2, 0.01);
+    assert_eq!(mismatches.len(), 2);
+    assert!(mismatches.iter().all(|m| m.path.starts_with("fruits[")));
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    enum Inventory {
+        Empty,
+        #[approx(map_keys)]
+        Stock(BTreeMap<u8, f32>),
+    }
+
+    let i1 = Inventory::Stock(BTreeMap::from([(1, 1.0), (2, 2.0)]));
+    let i2 = Inventory::Stock(BTreeMap::from([(1, 1.01), (2, 2.0)]));
+    approx::assert_abs_diff_eq!(i1, i2, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(i1, i2, epsilon = 0.001);
+    approx::assert_abs_diff_ne!(i1, Inventory::Empty);
+    let mismatches = i1.approx_mismatches(&i2, 0.001);
+    assert_eq!(mismatches[0].path, "Stock.0[1]");
+}
+
+#[test]
+fn derive_abs_diff_eq_into_iter_unordered() {
+    use std::collections::HashSet;
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct Particles {
+        #[approx(into_iter, unordered)]
+        positions: Vec<f64>,
+    }
+
+    // A greedy assignment would match 1.1 with 1.0 and fail to find a partner for 1.0
+    let p1 = Particles {
+        positions: vec![1.1, 1.0],
+    };
+    let p2 = Particles {
+        positions: vec![1.0, 1.2],
+    };
+    approx::assert_abs_diff_eq!(p1, p2, epsilon = 0.15);
+    approx::assert_abs_diff_ne!(p1, p2, epsilon = 0.05);
+
+    let p3 = Particles {
+        positions: vec![1.0, 1.0],
+    };
+    approx::assert_abs_diff_ne!(p1, p3, epsilon = 0.05);
+    let mismatches = p1.approx_mismatches(&p3, 0.05);
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].path, "positions[0]");
+    assert_eq!(mismatches[0].left, "1.1");
+    assert_eq!(mismatches[0].right, "<unmatched>");
+    assert_eq!(mismatches[1].path, "positions[1]");
+    assert_eq!(mismatches[1].left, "<unmatched>");
+
+    let p4 = Particles {
+        positions: vec![1.0],
+    };
+    approx::assert_abs_diff_ne!(p3, p4, epsilon = 10.0);
+    assert_eq!(p3.approx_mismatches(&p4, 10.0)[0].left, "2 elements");
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = u32)]
+    struct Ids {
+        #[approx(into_iter, unordered)]
+        ids: HashSet<u32>,
+    }
+
+    let i1 = Ids {
+        ids: HashSet::from([10, 20, 30]),
+    };
+    let i2 = Ids {
+        ids: HashSet::from([31, 19, 10]),
+    };
+    approx::assert_abs_diff_eq!(i1, i2, epsilon = 1);
+    approx::assert_abs_diff_ne!(i1, i2, epsilon = 0);
+}
+
+#[test]
+fn derive_abs_diff_eq_into_iter_options() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct Series {
+        #[approx(into_iter(length = shortest))]
+        prefix: Vec<f64>,
+        #[approx(into_iter(skip = 2, stride = 2, take = 2))]
+        window: Vec<f64>,
+        #[approx(into_iter(length = strict))]
+        exact: Vec<f64>,
+    }
+
+    let s1 = Series {
+        prefix: vec![1.0, 2.0],
+        window: vec![100.0, 100.0, 1.0, 100.0, 2.0, 100.0, 100.0],
+        exact: vec![1.0],
+    };
+    let s2 = Series {
+        prefix: vec![1.0, 2.0, 3.0],
+        window: vec![0.0, 0.0, 1.0, 0.0, 2.0],
+        exact: vec![1.0],
+    };
+    approx::assert_abs_diff_eq!(s1, s2);
+    assert!(s1.approx_mismatches(&s2, 0.0).is_empty());
+
+    let s3 = Series {
+        window: vec![0.0, 0.0, 1.0, 0.0, 2.5],
+        exact: vec![1.0, 2.0],
+        ..s2
+    };
+    approx::assert_abs_diff_ne!(s1, s3);
+    let mismatches = s1.approx_mismatches(&s3, 0.1);
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].path, "window[4]");
+    assert_eq!(mismatches[1].path, "exact");
+    assert_eq!(mismatches[1].right, "2 elements");
+
+    // Lengths which are known upfront fail without comparing any element
+    #[derive(PartialEq, Debug)]
+    struct Probe;
+
+    impl approx::AbsDiffEq for Probe {
+        type Epsilon = f64;
+
+        fn default_epsilon() -> f64 {
+            0.0
+        }
+
+        fn abs_diff_eq(&self, _: &Self, _: f64) -> bool {
+            panic!("elements should not be compared")
+        }
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct Probes(#[approx(into_iter)] Vec<Probe>);
+    approx::assert_abs_diff_ne!(Probes(vec![Probe, Probe]), Probes(vec![Probe]));
+    let mismatches = Probes(vec![Probe]).approx_mismatches(&Probes(vec![]), 0.0);
+    assert_eq!(mismatches[0].left, "1 elements");
+}
+
+#[test]
+fn derive_abs_diff_eq_into_iter_norm() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct State {
+        #[approx(into_iter, norm = l2)]
+        l2: Vec<f64>,
+        #[approx(into_iter, norm = l1)]
+        l1: [f64; 3],
+        #[approx(into_iter, norm = linf)]
+        linf: Vec<f64>,
+    }
+
+    let s1 = State {
+        l2: vec![0.0, 0.0],
+        l1: [1.0, 2.0, 3.0],
+        linf: vec![1.0, 1.0, 1.0],
+    };
+    let s2 = State {
+        l2: vec![0.3, 0.4],
+        l1: [1.1, 2.2, 2.8],
+        linf: vec![1.3, 0.8, 1.5],
+    };
+    approx::assert_abs_diff_eq!(s1, s2, epsilon = 0.5 + 1e-12);
+    approx::assert_abs_diff_ne!(s1, s2, epsilon = 0.49);
+
+    let mismatches = s1.approx_mismatches(&s2, 0.49);
+    assert_eq!(mismatches.len(), 3);
+    assert_eq!(mismatches[0].path, "l2");
+    assert_eq!(mismatches[0].mode, "norm");
+    assert_eq!(mismatches[0].diff.as_deref(), Some("0.5"));
+    assert_eq!(mismatches[2].diff.as_deref(), Some("0.5"));
+
+    let s3 = State {
+        l2: vec![0.0],
+        ..s2
+    };
+    approx::assert_abs_diff_ne!(s1, s3, epsilon = 10.0);
+    assert_eq!(s1.approx_mismatches(&s3, 10.0)[0].left, "different length");
+}
+
+#[test]
+fn derive_abs_diff_eq_periodic() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Particle {
+        #[approx(periodic = 360.0)]
+        angle: f64,
+        #[approx(equal)]
+        box_length: f64,
+        #[approx(into_iter, periodic = self.box_length)]
+        position: [f64; 2],
+    }
+
+    let p1 = Particle {
+        angle: 359.9,
+        box_length: 10.0,
+        position: [0.001, 5.0],
+    };
+    let p2 = Particle {
+        angle: 0.1,
+        box_length: 10.0,
+        position: [9.999, 5.0],
+    };
+    approx::assert_abs_diff_eq!(p1, p2, epsilon = 0.25);
+    approx::assert_abs_diff_ne!(p1, p2, epsilon = 0.1);
+
+    let mismatches = p1.approx_mismatches(&p2, 0.1);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "angle");
+    assert_eq!(mismatches[0].mode, "periodic");
+    assert!(mismatches[0].diff.as_ref().unwrap().starts_with("0.2"));
+
+    let p3 = Particle {
+        position: [5.0, 5.0],
+        ..p2
+    };
+    approx::assert_abs_diff_ne!(p1, p3, epsilon = 1.0);
+    assert_eq!(p1.approx_mismatches(&p3, 1.0)[0].path, "position[0]");
+}
+
+#[test]
+fn derive_abs_diff_eq_special_values() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Reading {
+        #[approx(nan_equal)]
+        value: f64,
+        #[approx(inf_equal)]
+        limit: f64,
+        #[approx(into_iter, nan_equal)]
+        samples: Vec<f64>,
+        #[approx(map = |x: &Option<f64>| *x, nan_equal)]
+        offset: Option<f64>,
+        other: f64,
+    }
+
+    let r1 = Reading {
+        value: f64::NAN,
+        limit: f64::INFINITY,
+        samples: vec![1.0, f64::NAN],
+        offset: Some(f64::NAN),
+        other: 1.0,
+    };
+    let r2 = Reading {
+        value: f64::NAN,
+        limit: f64::INFINITY,
+        samples: vec![1.0, f64::NAN],
+        offset: Some(f64::NAN),
+        other: 1.0,
+    };
+    approx::assert_abs_diff_eq!(r1, r2);
+    let r3 = Reading {
+        limit: f64::NEG_INFINITY,
+        ..r2
+    };
+    approx::assert_abs_diff_ne!(r1, r3);
+    assert_eq!(r1.approx_mismatches(&r3, 0.0)[0].path, "limit");
+    let r4 = Reading {
+        other: f64::NAN,
+        limit: f64::INFINITY,
+        ..r3
+    };
+    approx::assert_abs_diff_ne!(r1, r4);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(nan_equal, inf_equal)]
+    enum Measurement {
+        Single(f32),
+        Pair { low: f32, high: f32 },
+    }
+
+    approx::assert_abs_diff_eq!(Measurement::Single(f32::NAN), Measurement::Single(f32::NAN));
+    approx::assert_abs_diff_eq!(
+        Measurement::Pair {
+            low: f32::NEG_INFINITY,
+            high: f32::NAN
+        },
+        Measurement::Pair {
+            low: f32::NEG_INFINITY,
+            high: f32::NAN
+        }
+    );
+    approx::assert_abs_diff_ne!(
+        Measurement::Single(f32::INFINITY),
+        Measurement::Single(f32::NAN)
+    );
+}
+
+#[test]
+fn derive_abs_diff_eq_compare() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(default_max_relative = 1e-6, mismatches)]
+    struct Orbit {
+        #[approx(compare = relative)]
+        semi_major_axis: f64,
+        #[approx(compare = ulps, static_max_ulps = 4)]
+        eccentricity: f64,
+        period: f64,
+    }
+
+    let o1 = Orbit {
+        semi_major_axis: 1.5e11,
+        eccentricity: 0.0167,
+        period: 365.25,
+    };
+    let o2 = Orbit {
+        semi_major_axis: 1.5e11 + 1e4,
+        eccentricity: f64::from_bits(0.0167_f64.to_bits() + 2),
+        period: 365.3,
+    };
+    approx::assert_abs_diff_eq!(o1, o2, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(o1, o2, epsilon = 1e-6);
+    let mismatches = o1.approx_mismatches(&o2, 1e-6);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "period");
+
+    let o3 = Orbit {
+        eccentricity: 0.0168,
+        ..o1
+    };
+    let o4 = Orbit {
+        semi_major_axis: 1.6e11,
+        ..o1
+    };
+    approx::assert_abs_diff_ne!(o1, o3, epsilon = 1e-6);
+    approx::assert_abs_diff_ne!(o1, o4, epsilon = 1e6);
+}
+
+#[test]
+fn derive_abs_diff_eq_tolerance_expressions() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Measurement {
+        #[approx(epsilon = 2.0 * (self.sigma + other.sigma))]
+        value: f64,
+        #[approx(epsilon = epsilon / 10.0)]
+        sigma: f64,
+    }
+
+    let m1 = Measurement {
+        value: 10.0,
+        sigma: 0.5,
+    };
+    let m2 = Measurement {
+        value: 11.9,
+        sigma: 0.5,
+    };
+    let m3 = Measurement {
+        value: 12.1,
+        sigma: 0.5,
+    };
+    approx::assert_abs_diff_eq!(m1, m2);
+    approx::assert_abs_diff_ne!(m1, m3);
+    let mismatches = m1.approx_mismatches(&m3, 0.0);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "value");
+    assert_eq!(mismatches[0].epsilon.as_deref(), Some("2.0"));
+
+    let m4 = Measurement {
+        value: 10.0,
+        sigma: 0.6,
+    };
+    approx::assert_abs_diff_ne!(m1, m4, epsilon = 0.5);
+    approx::assert_abs_diff_eq!(m1, m4, epsilon = 1.5);
+}
+
+#[test]
+fn derive_abs_diff_eq_skip_if() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Body {
+        position: f32,
+        #[approx(equal)]
+        is_moving: bool,
+        #[approx(skip_if = !self.is_moving && !other.is_moving)]
+        velocity: f32,
+    }
+
+    let b1 = Body {
+        position: 1.0,
+        is_moving: false,
+        velocity: 0.0,
+    };
+    let b2 = Body {
+        position: 1.0,
+        is_moving: false,
+        velocity: 5.0,
+    };
+    approx::assert_abs_diff_eq!(b1, b2);
+    assert!(b1.approx_mismatches(&b2, 0.0).is_empty());
+    let b3 = Body {
+        is_moving: true,
+        ..b1
+    };
+    let b4 = Body {
+        is_moving: true,
+        ..b2
+    };
+    approx::assert_abs_diff_ne!(b3, b4);
+    assert_eq!(b3.approx_mismatches(&b4, 0.0)[0].path, "velocity");
+
+    #[derive(PartialEq, Debug)]
+    enum Status {
+        Ok,
+        Failed,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64)]
+    enum Outcome {
+        Single {
+            #[approx(equal)]
+            status: Status,
+            value: f64,
+            #[approx(compare_if = self.failed() || other.failed())]
+            error: f64,
+        },
+    }
+
+    impl Outcome {
+        fn failed(&self) -> bool {
+            let Outcome::Single { status, .. } = self;
+            *status == Status::Failed
+        }
+    }
+
+    let o1 = Outcome::Single {
+        status: Status::Ok,
+        value: 1.0,
+        error: 0.0,
+    };
+    let o2 = Outcome::Single {
+        status: Status::Ok,
+        value: 1.0,
+        error: 3.0,
+    };
+    let o3 = Outcome::Single {
+        status: Status::Failed,
+        value: 1.0,
+        error: 3.0,
+    };
+    let o4 = Outcome::Single {
+        status: Status::Failed,
+        value: 1.0,
+        error: 0.0,
+    };
+    approx::assert_abs_diff_eq!(o1, o2);
+    approx::assert_abs_diff_ne!(o3, o4);
+    approx::assert_abs_diff_eq!(o3, o4, epsilon = 3.0);
+}
+
+#[test]
+fn derive_abs_diff_eq_groups() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct State {
+        #[approx(group = "kinematics")]
+        position: f64,
+        #[approx(group = "kinematics", group = "energy")]
+        velocity: f64,
+        #[approx(group = "chemistry")]
+        concentration: f64,
+        temperature: f64,
+    }
+
+    let s1 = State {
+        position: 1.0,
+        velocity: 2.0,
+        concentration: 0.1,
+        temperature: 300.0,
+    };
+    let s2 = State {
+        position: 1.05,
+        velocity: 2.0,
+        concentration: 0.5,
+        temperature: 310.0,
+    };
+    assert!(s1.abs_diff_eq_kinematics(&s2, 0.1));
+    assert!(!s1.abs_diff_eq_kinematics(&s2, 0.01));
+    assert!(s1.abs_diff_eq_energy(&s2, 0.0));
+    assert!(!s1.abs_diff_eq_chemistry(&s2, 0.1));
+    approx::assert_abs_diff_ne!(s1, s2, epsilon = 0.1);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    enum Shape {
+        Circle {
+            #[approx(group = "size")]
+            radius: f32,
+            center: f32,
+        },
+        Point(f32),
+    }
+
+    let c1 = Shape::Circle {
+        radius: 1.0,
+        center: 0.0,
+    };
+    let c2 = Shape::Circle {
+        radius: 1.0,
+        center: 5.0,
+    };
+    assert!(c1.abs_diff_eq_size(&c2, 0.0));
+    assert!(!c1.abs_diff_eq_size(&Shape::Point(1.0), 1.0));
+    assert!(Shape::Point(1.0).abs_diff_eq_size(&Shape::Point(2.0), 0.0));
+}
+
+#[test]
+fn derive_abs_diff_eq_profiles() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(
+        profile(strict(default_epsilon = 1e-8)),
+        profile(gpu_offload(default_epsilon = 1e-2))
+    )]
+    struct State {
+        position: f64,
+        #[approx(profile(gpu_offload(static_epsilon = 0.5)))]
+        temperature: f64,
+    }
+
+    let s1 = State {
+        position: 1.0,
+        temperature: 300.0,
+    };
+    let s2 = State {
+        position: 1.005,
+        temperature: 300.2,
+    };
+    assert!(!s1.abs_diff_eq_profile(&s2, StateProfile::Strict));
+    assert!(s1.abs_diff_eq_profile(&s2, StateProfile::GpuOffload));
+    let s3 = State {
+        position: 1.0,
+        temperature: 301.0,
+    };
+    assert!(!s1.abs_diff_eq_profile(&s3, StateProfile::GpuOffload));
+    approx::assert_abs_diff_ne!(s1, s2);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(profile(loose(default_epsilon = 0.5)))]
+    enum Shape {
+        Circle { radius: f32 },
+        Point(f32),
+    }
+
+    let c1 = Shape::Circle { radius: 1.0 };
+    let c2 = Shape::Circle { radius: 1.2 };
+    assert!(c1.abs_diff_eq_profile(&c2, ShapeProfile::Loose));
+    assert!(!c1.abs_diff_eq_profile(&Shape::Point(1.0), ShapeProfile::Loose));
+    approx::assert_abs_diff_ne!(c1, c2);
+}
+
+#[test]
+fn derive_abs_diff_eq_computed_properties() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(also(energy = |s: &Self| s.total_energy(), epsilon = 1e-6))]
+    #[approx(also(center = |s| s.position), mismatches)]
+    struct System {
+        #[approx(skip)]
+        position: f64,
+        mass: f64,
+        velocity: f64,
+    }
+
+    impl System {
+        fn total_energy(&self) -> f64 {
+            0.5 * self.mass * self.velocity * self.velocity
+        }
+    }
+
+    let s1 = System {
+        position: 0.0,
+        mass: 2.0,
+        velocity: 1.0,
+    };
+    let s2 = System {
+        position: 0.05,
+        mass: 2.0,
+        velocity: 1.001,
+    };
+    approx::assert_abs_diff_ne!(s1, s2, epsilon = 0.1);
+    let s3 = System {
+        position: 0.05,
+        mass: 2.0,
+        velocity: 1.0,
+    };
+    approx::assert_abs_diff_eq!(s1, s3, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(s1, s3, epsilon = 0.01);
+
+    let mismatches = s1.approx_mismatches(&s2, 0.1);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "energy");
+    assert_eq!(mismatches[0].mode, "also");
+    assert_eq!(mismatches[0].left, "1.0");
+    assert_eq!(mismatches[0].epsilon.as_deref(), Some("1e-6"));
+    let mismatches = s1.approx_mismatches(&s3, 0.01);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "center");
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(also(area = |s: &Self| s.area()))]
+    enum Shape {
+        Circle(f64),
+        Square(f64),
+    }
+
+    impl Shape {
+        fn area(&self) -> f64 {
+            match self {
+                Shape::Circle(r) => 3.0 * r * r,
+                Shape::Square(a) => a * a,
+            }
+        }
+    }
+
+    approx::assert_abs_diff_eq!(Shape::Circle(1.0), Shape::Circle(1.0), epsilon = 0.0);
+    approx::assert_abs_diff_ne!(Shape::Circle(1.0), Shape::Circle(1.1), epsilon = 0.2);
+    approx::assert_abs_diff_eq!(Shape::Circle(1.0), Shape::Circle(1.1), epsilon = 0.7);
+    approx::assert_abs_diff_ne!(Shape::Circle(1.0), Shape::Square(1.0), epsilon = 0.7);
+}
+
+mod geometry {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct Point {
+        pub x: f64,
+        pub y: f64,
+    }
+
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub enum Shape {
+        Circle { center: Point, radius: f64 },
+        Dot(Point),
+    }
+}
+
+#[test]
+fn derive_abs_diff_eq_remote() {
+    use geometry::{Point, Shape};
+
+    #[derive(AbsDiffEq)]
+    #[approx(remote = "geometry::Point")]
+    struct PointDef {
+        x: f64,
+        #[approx(static_epsilon = 0.5)]
+        y: f64,
+    }
+
+    #[derive(AbsDiffEq)]
+    #[approx(remote = "geometry::Shape", epsilon_type = f64)]
+    enum ShapeDef {
+        Circle {
+            #[approx(with = PointDef)]
+            center: Point,
+            radius: f64,
+        },
+        Dot(#[approx(with = PointDef)] Point),
+    }
+
+    let p1 = Point { x: 1.0, y: 2.0 };
+    let p2 = Point { x: 1.05, y: 2.3 };
+    assert!(PointDef::abs_diff_eq(&p1, &p2, 0.1));
+    assert!(!PointDef::abs_diff_eq(&p1, &p2, 0.01));
+
+    let c1 = Shape::Circle {
+        center: p1,
+        radius: 1.0,
+    };
+    let c2 = Shape::Circle {
+        center: p2,
+        radius: 1.05,
+    };
+    assert!(ShapeDef::abs_diff_eq(&c1, &c2, 0.1));
+    assert!(!ShapeDef::abs_diff_eq(&c1, &Shape::Dot(p1), 0.1));
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64)]
+    struct Scene {
+        #[approx(with = ShapeDef)]
+        shape: Shape,
+        scale: f64,
+    }
+
+    let s1 = Scene {
+        shape: c1,
+        scale: 1.0,
+    };
+    let s2 = Scene {
+        shape: c2,
+        scale: 1.0,
+    };
+    approx::assert_abs_diff_eq!(s1, s2, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(s1, s2, epsilon = 0.01);
+}
+
+#[test]
+fn derive_abs_diff_eq_transparent() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(transparent)]
+    struct Meters(f64);
+
+    approx::assert_abs_diff_eq!(Meters(1.0), Meters(1.0 + f64::EPSILON));
+    approx::assert_abs_diff_ne!(Meters(1.0), Meters(1.1));
+    approx::assert_abs_diff_eq!(Meters(1.0), Meters(1.1), epsilon = 0.2);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(transparent)]
+    struct Tagged<T> {
+        value: T,
+        #[approx(skip)]
+        tag: &'static str,
+    }
+
+    let t1 = Tagged {
+        value: 1.0f32,
+        tag: "first",
+    };
+    let t2 = Tagged {
+        value: 1.05f32,
+        tag: "second",
+    };
+    assert_eq!(
+        <Tagged<f32> as approx::AbsDiffEq>::default_epsilon(),
+        f32::EPSILON
+    );
+    approx::assert_abs_diff_eq!(t1, t2, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(t1, t2, epsilon = 0.01);
+}
+
+#[test]
+fn derive_abs_diff_eq_via() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(via = u32, convert = time_to_days)]
+    enum Time {
+        Weeks(u32),
+        Days(u32),
+    }
+
+    fn time_to_days(time: &Time) -> u32 {
+        match time {
+            Time::Weeks(w) => 7 * w,
+            Time::Days(d) => *d,
+        }
+    }
+
+    approx::assert_abs_diff_eq!(Time::Weeks(1), Time::Days(7));
+    approx::assert_abs_diff_ne!(Time::Weeks(1), Time::Days(8));
+    approx::assert_abs_diff_eq!(Time::Weeks(1), Time::Days(8), epsilon = 1);
+    assert_eq!(<Time as approx::AbsDiffEq>::default_epsilon(), 0);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Cartesian {
+        x: f64,
+        y: f64,
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(via = Cartesian)]
+    struct Polar {
+        radius: f64,
+        angle: f64,
+    }
+
+    impl From<&Polar> for Cartesian {
+        fn from(polar: &Polar) -> Self {
+            Cartesian {
+                x: polar.radius * polar.angle.cos(),
+                y: polar.radius * polar.angle.sin(),
+            }
+        }
+    }
+
+    let p1 = Polar {
+        radius: 1.0,
+        angle: 0.0,
+    };
+    let p2 = Polar {
+        radius: 1.0,
+        angle: 2.0 * std::f64::consts::PI,
+    };
+    let p3 = Polar {
+        radius: -1.0,
+        angle: std::f64::consts::PI,
+    };
+    approx::assert_abs_diff_eq!(p1, p2, epsilon = 1e-12);
+    approx::assert_abs_diff_eq!(p1, p3, epsilon = 1e-12);
+    approx::assert_abs_diff_ne!(
+        p1,
+        Polar {
+            radius: 1.1,
+            angle: 0.0
+        },
+        epsilon = 1e-12
+    );
+}
+
+#[test]
+fn derive_abs_diff_eq_cast_conversions() {
+    #[derive(Clone, PartialEq, Debug)]
+    struct Meters(f32);
+
+    impl From<Meters> for f64 {
+        fn from(meters: Meters) -> Self {
+            meters.0 as f64
+        }
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Route {
+        duration: f64,
+        #[approx(cast_field = into)]
+        length: Meters,
+    }
+
+    let r1 = Route {
+        duration: 1.0,
+        length: Meters(100.0),
+    };
+    let r2 = Route {
+        duration: 1.0,
+        length: Meters(100.5),
+    };
+    approx::assert_abs_diff_ne!(r1, r2);
+    approx::assert_abs_diff_eq!(r1, r2, epsilon = 0.6);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Counter {
+        count: i32,
+        #[approx(cast_field = try_into)]
+        total: u64,
+    }
+
+    let c1 = Counter { count: 1, total: 5 };
+    let c2 = Counter { count: 1, total: 6 };
+    approx::assert_abs_diff_eq!(c1, c2, epsilon = 1);
+    // Values which can not be converted are never equal
+    let c3 = Counter {
+        count: 1,
+        total: u64::MAX,
+    };
+    approx::assert_abs_diff_ne!(c3, c3, epsilon = i32::MAX);
+
+    #[derive(AbsDiffEq, Clone, PartialEq, Debug)]
+    #[approx(transparent)]
+    struct Seconds(f64);
+
+    impl From<u32> for Seconds {
+        fn from(seconds: u32) -> Self {
+            Seconds(seconds as f64)
+        }
+    }
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Lap {
+        offset: f64,
+        #[approx(cast_field(to = Seconds, by = into))]
+        seconds: u32,
+    }
+
+    let l1 = Lap {
+        offset: 0.0,
+        seconds: 10,
+    };
+    let l2 = Lap {
+        offset: 0.0,
+        seconds: 12,
+    };
+    approx::assert_abs_diff_ne!(l1, l2, epsilon = 1.0);
+    approx::assert_abs_diff_eq!(l1, l2, epsilon = 2.0);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(transparent)]
+    struct Length(f32);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Segment {
+        position: f64,
+        #[approx(cast_value(to = f32))]
+        length: Length,
+    }
+
+    let s1 = Segment {
+        position: 0.0,
+        length: Length(1.0),
+    };
+    let s2 = Segment {
+        position: 0.0,
+        length: Length(1.25),
+    };
+    approx::assert_abs_diff_ne!(s1, s2, epsilon = 0.2);
+    approx::assert_abs_diff_eq!(s1, s2, epsilon = 0.3);
+
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Sample {
+        index: i64,
+        #[approx(cast_value = try_into)]
+        level: u8,
+    }
+
+    let a = Sample { index: 0, level: 7 };
+    let b = Sample { index: 0, level: 9 };
+    approx::assert_abs_diff_eq!(a, b, epsilon = 2);
+    // The epsilon value does not fit into `u8`
+    approx::assert_abs_diff_ne!(a, a, epsilon = 300);
+}
@@ -111,6 +111,27 @@ fn derive_rel_diff_eq_generics() {
     approx::assert_relative_eq!(p1, p2, max_relative = 0.01);
 }
 
+#[test]
+fn derive_rel_diff_eq_generics_iter() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    enum Signal<F, G> {
+        Constant(F),
+        Sampled {
+            #[approx(into_iter)]
+            values: Vec<G>,
+        },
+    }
+    let s1: Signal<f32, f32> = Signal::Sampled {
+        values: vec![1.0, 2.0],
+    };
+    let s2 = Signal::Sampled {
+        values: vec![1.0, 2.02],
+    };
+    approx::assert_relative_eq!(s1, s2, max_relative = 0.02);
+    approx::assert_relative_ne!(s1, s2, max_relative = 0.001);
+    approx::assert_relative_ne!(s1, Signal::Constant(1.0));
+}
+
 #[test]
 fn derive_rel_diff_eq_equal_1() {
     #[derive(RelativeEq, PartialEq, Debug)]
@@ -276,19 +297,6 @@ fn derive_relative_mapping_function() {
     approx::assert_relative_eq!(d1, d2, epsilon = 0.0);
 }
 
-#[test]
-fn derive_relative_equal_higher_priority_than_mapping() {
-    #[derive(RelativeEq, PartialEq, Debug)]
-    struct Length {
-        #[approx(equal)]
-        #[approx(map = |x: &f32| Some(2.0*x))]
-        meters: f32,
-    }
-    let l1 = Length { meters: 3.0 };
-    let l2 = Length { meters: 3.0001 };
-    approx::assert_relative_ne!(l1, l2, epsilon = 0.001);
-}
-
 #[test]
 fn derive_rel_diff_enum() {
     #[derive(RelativeEq, PartialEq, Debug)]
@@ -457,3 +465,699 @@ fn epsilon_mapping() {
     };
     approx::assert_relative_ne!(a1, a2);
 }
+
+#[test]
+fn derive_rel_diff_eq_rhs() {
+    #[derive(Debug)]
+    struct Reference {
+        energy: f64,
+        samples: Vec<f64>,
+    }
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(rhs = Reference)]
+    struct Simulation {
+        energy: f64,
+        #[approx(into_iter)]
+        samples: [f64; 2],
+    }
+
+    impl PartialEq<Reference> for Simulation {
+        fn eq(&self, other: &Reference) -> bool {
+            self.energy == other.energy && self.samples[..] == other.samples[..]
+        }
+    }
+
+    let s = Simulation {
+        energy: 100.0,
+        samples: [1.0, 2.0],
+    };
+    let r = Reference {
+        energy: 101.0,
+        samples: vec![1.01, 2.0],
+    };
+    approx::assert_relative_eq!(s, r, max_relative = 0.02);
+    approx::assert_relative_ne!(s, r, max_relative = 0.001);
+}
+
+#[test]
+fn derive_rel_diff_eq_epsilon_struct() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_struct)]
+    #[approx(default_max_relative = 0.1)]
+    struct Reaction {
+        rate: f64,
+        #[approx(static_max_relative = 0.001)]
+        concentration: f32,
+    }
+
+    let r1 = Reaction {
+        rate: 100.0,
+        concentration: 1.0,
+    };
+    let r2 = Reaction {
+        rate: 105.0,
+        concentration: 1.01,
+    };
+
+    approx::assert_relative_ne!(r1, r2);
+    approx::assert_relative_eq!(
+        r1,
+        r2,
+        max_relative = ReactionEpsilon {
+            rate: 0.1,
+            concentration: 0.02,
+        }
+    );
+}
+
+#[test]
+fn derive_rel_diff_eq_epsilon_struct_nested_default() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_struct, default_max_relative = 0.2)]
+    struct Concentration {
+        value: f64,
+    }
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_struct, default_epsilon = 0.0, default_max_relative = 0.01)]
+    struct Sample {
+        temperature: f64,
+        concentration: Concentration,
+    }
+
+    let max_relative = <Sample as approx::RelativeEq>::default_max_relative();
+    assert_eq!(max_relative.temperature, 0.01);
+    assert_eq!(max_relative.concentration.value, 0.2);
+    assert_eq!(SampleEpsilon::default().concentration.value, f64::EPSILON);
+
+    let s1 = Sample {
+        temperature: 300.0,
+        concentration: Concentration { value: 1.0 },
+    };
+    let s2 = Sample {
+        temperature: 301.0,
+        concentration: Concentration { value: 1.1 },
+    };
+    approx::assert_relative_eq!(s1, s2);
+}
+
+#[test]
+fn derive_rel_diff_eq_mismatches() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Inner {
+        value: f64,
+    }
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Outer {
+        inner: Inner,
+        #[approx(static_epsilon = 0.5)]
+        other: f64,
+    }
+
+    let o1 = Outer {
+        inner: Inner { value: 1.0 },
+        other: 1.0,
+    };
+    let o2 = Outer {
+        inner: Inner { value: 1.2 },
+        other: 2.0,
+    };
+    let mismatches = o1.approx_relative_mismatches(&o2, 0.01, 0.1);
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].path, "inner.value");
+    assert_eq!(mismatches[0].max_relative.as_deref(), Some("0.1"));
+    assert_eq!(mismatches[1].path, "other");
+    assert_eq!(mismatches[1].epsilon.as_deref(), Some("0.5"));
+    assert!(o1.approx_relative_mismatches(&o2, 0.01, 0.2)[0].path == "other");
+    assert_eq!(o1.approx_mismatches(&o2, 0.1).len(), 2);
+
+    // Both reports share the generated type of their entries
+    fn paths(mismatches: &[OuterMismatch]) -> Vec<&str> {
+        mismatches.iter().map(|m| m.path.as_str()).collect()
+    }
+    assert_eq!(
+        paths(&o1.approx_relative_mismatches(&o2, 0.01, 0.1)),
+        paths(&o1.approx_mismatches(&o2, 0.1))
+    );
+    let stored: Vec<OuterMismatch> = o1.approx_mismatches(&o2, 0.1);
+    assert_eq!(stored[0].max_relative, None);
+}
+
+#[test]
+fn derive_rel_diff_eq_assert_fields() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Measurement {
+        value: f64,
+        #[approx(equal)]
+        unit: &'static str,
+    }
+
+    let m1 = Measurement {
+        value: 100.0,
+        unit: "m",
+    };
+    let m2 = Measurement {
+        value: 101.0,
+        unit: "m",
+    };
+    assert_fields_relative_eq!(m1, m2, max_relative = 0.02);
+    assert_fields_relative_eq!(m1, m2, epsilon = 0.0, max_relative = 0.02);
+
+    let result = std::panic::catch_unwind(|| assert_fields_relative_eq!(m1, m2));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("value | 100.0 | 101.0 | 1.0"));
+    assert!(message.contains("(max_relative "));
+}
+
+#[test]
+fn derive_rel_diff_eq_assert_fields_without_report() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    struct Pressure {
+        value: f64,
+    }
+
+    let p1 = Pressure { value: 100.0 };
+    let p2 = Pressure { value: 101.0 };
+    assert_fields_abs_diff_eq!(p1, p2, epsilon = 1.5);
+    assert_fields_relative_eq!(p1, p2, max_relative = 0.02);
+
+    let result = std::panic::catch_unwind(|| assert_fields_relative_eq!(p1, p2));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert!(message.contains("(value) | Pressure { value: 100.0 } | Pressure { value: 101.0 } | -"));
+    assert!(message.contains("(max_relative "));
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Timestamp(u64);
+
+mod timestamp {
+    pub fn abs_diff_eq(a: &super::Timestamp, b: &super::Timestamp, epsilon: f64) -> bool {
+        a.0.abs_diff(b.0) as f64 <= epsilon
+    }
+
+    pub fn relative_eq(
+        a: &super::Timestamp,
+        b: &super::Timestamp,
+        epsilon: f64,
+        max_relative: f64,
+    ) -> bool {
+        abs_diff_eq(a, b, epsilon) || a.0.abs_diff(b.0) as f64 <= max_relative * a.0 as f64
+    }
+}
+
+#[test]
+fn derive_rel_diff_eq_with() {
+    fn same_day(a: &Timestamp, b: &Timestamp, _: f64) -> bool {
+        a.0 / 86_400 == b.0 / 86_400
+    }
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Event {
+        value: f64,
+        #[approx(with = timestamp)]
+        start: Timestamp,
+        #[approx(compare_with = same_day)]
+        end: Timestamp,
+    }
+
+    let e1 = Event {
+        value: 1.0,
+        start: Timestamp(1000),
+        end: Timestamp(10),
+    };
+    let e2 = Event {
+        value: 1.0,
+        start: Timestamp(1010),
+        end: Timestamp(20),
+    };
+    approx::assert_abs_diff_eq!(e1, e2, epsilon = 10.0);
+    approx::assert_abs_diff_ne!(e1, e2, epsilon = 5.0);
+    approx::assert_relative_eq!(e1, e2, epsilon = 5.0, max_relative = 0.1);
+    approx::assert_relative_ne!(e1, e2, epsilon = 5.0, max_relative = 0.001);
+
+    let e3 = Event {
+        end: Timestamp(86_400),
+        ..e2
+    };
+    approx::assert_abs_diff_ne!(e1, e3, epsilon = 10.0);
+    let mismatches = e1.approx_mismatches(&e3, 10.0);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "end");
+    assert_eq!(mismatches[0].mode, "with");
+    assert_eq!(mismatches[0].left, "Timestamp(10)");
+}
+
+#[test]
+fn derive_rel_diff_eq_map_keys() {
+    use std::collections::HashMap;
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    struct Populations {
+        #[approx(map_keys)]
+        cities: HashMap<&'static str, f64>,
+    }
+
+    let p1 = Populations {
+        cities: HashMap::from([("Berlin", 3.7e6), ("Paris", 2.1e6)]),
+    };
+    let p2 = Populations {
+        cities: HashMap::from([("Paris", 2.15e6), ("Berlin", 3.6e6)]),
+    };
+    approx::assert_relative_eq!(p1, p2, max_relative = 0.05);
+    approx::assert_relative_ne!(p1, p2, max_relative = 0.01);
+    let p3 = Populations {
+        cities: HashMap::from([("Berlin", 3.7e6), ("Rome", 2.1e6)]),
+    };
+    approx::assert_relative_ne!(p1, p3, max_relative = 1.0);
+}
+
+#[test]
+fn derive_rel_diff_eq_into_iter_unordered() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f32, mismatches)]
+    enum Cloud {
+        #[approx(into_iter, unordered)]
+        Points(Vec<f32>),
+    }
+
+    let c1 = Cloud::Points(vec![100.0, 200.0, 300.0]);
+    let c2 = Cloud::Points(vec![301.0, 99.0, 201.0]);
+    approx::assert_relative_eq!(c1, c2, max_relative = 0.011);
+    approx::assert_relative_ne!(c1, c2, max_relative = 0.001);
+    assert_eq!(c1.approx_relative_mismatches(&c2, 0.0, 0.001).len(), 6);
+}
+
+#[test]
+fn derive_rel_diff_eq_into_iter_norm() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64)]
+    struct Field {
+        #[approx(into_iter, norm = l2)]
+        values: Vec<f64>,
+    }
+
+    // A single tiny component fails elementwise but not by norm
+    let f1 = Field {
+        values: vec![300.0, 400.0, 1e-9],
+    };
+    let f2 = Field {
+        values: vec![300.0, 400.0, 2e-9],
+    };
+    approx::assert_relative_eq!(f1, f2, epsilon = 0.0, max_relative = 1e-9);
+    let f3 = Field {
+        values: vec![303.0, 404.0, 1e-9],
+    };
+    approx::assert_relative_eq!(f1, f3, epsilon = 0.0, max_relative = 0.011);
+    approx::assert_relative_ne!(f1, f3, epsilon = 0.0, max_relative = 0.009);
+}
+
+#[test]
+fn derive_rel_diff_eq_periodic() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    enum Phase {
+        #[approx(periodic = 2.0 * std::f32::consts::PI)]
+        Radians(f32),
+    }
+
+    let p1 = Phase::Radians(0.01);
+    let p2 = Phase::Radians(2.0 * std::f32::consts::PI - 0.01);
+    approx::assert_relative_eq!(p1, p2, epsilon = 0.03);
+    approx::assert_relative_ne!(p1, p2, epsilon = 0.01, max_relative = 0.001);
+    approx::assert_relative_eq!(p1, p2, epsilon = 0.01, max_relative = 0.01);
+}
+
+#[test]
+fn derive_rel_diff_eq_special_values() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(nan_equal)]
+    struct Spectrum {
+        peak: f32,
+        #[approx(into_iter, inf_equal)]
+        bins: [f32; 3],
+    }
+
+    let s1 = Spectrum {
+        peak: f32::NAN,
+        bins: [f32::NAN, f32::INFINITY, 1.0],
+    };
+    let s2 = Spectrum {
+        peak: f32::NAN,
+        bins: [f32::NAN, f32::INFINITY, 1.0],
+    };
+    approx::assert_relative_eq!(s1, s2);
+    let s3 = Spectrum {
+        bins: [f32::NAN, f32::INFINITY, 1.1],
+        ..s2
+    };
+    approx::assert_relative_ne!(s1, s3);
+    approx::assert_relative_eq!(s1, s3, max_relative = 0.1);
+}
+
+#[test]
+fn derive_rel_diff_eq_compare() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Calibration {
+        #[approx(compare = abs)]
+        offset: f32,
+        scale: f32,
+        #[approx(into_iter, compare = ulps)]
+        samples: Vec<f32>,
+    }
+
+    let c1 = Calibration {
+        offset: 1.0,
+        scale: 100.0,
+        samples: vec![1.0, 2.0],
+    };
+    let c2 = Calibration {
+        offset: 1.1,
+        scale: 110.0,
+        samples: vec![1.0 + f32::EPSILON, 2.0],
+    };
+    approx::assert_relative_eq!(c1, c2, epsilon = 10.0, max_relative = 0.0);
+    approx::assert_relative_eq!(c1, c2, epsilon = 0.15, max_relative = 0.1);
+    approx::assert_relative_ne!(c1, c2, epsilon = 0.0, max_relative = 0.2);
+    let mismatches = c1.approx_relative_mismatches(&c2, 0.0, 0.2);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "offset");
+}
+
+#[test]
+fn derive_rel_diff_eq_tolerance_expressions() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    struct Estimate(
+        #[approx(max_relative = max_relative * (1.0 + self.1.max(other.1)))] f32,
+        f32,
+    );
+
+    let e1 = Estimate(100.0, 0.0);
+    let e2 = Estimate(101.5, 0.0);
+    let e3 = Estimate(100.0, 1.0);
+    let e4 = Estimate(101.5, 1.0);
+    approx::assert_relative_ne!(e1, e2, epsilon = 0.0, max_relative = 0.01);
+    approx::assert_relative_eq!(e3, e4, epsilon = 0.0, max_relative = 0.01);
+    approx::assert_relative_ne!(e3, e4, epsilon = 0.0, max_relative = 0.005);
+}
+
+#[test]
+fn derive_rel_diff_eq_skip_if() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct Fit {
+        #[approx(equal)]
+        converged: bool,
+        #[approx(compare_if = self.converged)]
+        residual: f64,
+    }
+
+    let f1 = Fit {
+        converged: false,
+        residual: 1.0,
+    };
+    let f2 = Fit {
+        converged: false,
+        residual: 2.0,
+    };
+    approx::assert_relative_eq!(f1, f2);
+    let f3 = Fit {
+        converged: true,
+        ..f1
+    };
+    let f4 = Fit {
+        converged: true,
+        ..f2
+    };
+    approx::assert_relative_ne!(f3, f4);
+    assert_eq!(
+        f3.approx_relative_mismatches(&f4, 0.0, 0.1)[0].path,
+        "residual"
+    );
+}
+
+#[test]
+fn derive_rel_diff_eq_groups() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    struct Reactor {
+        #[approx(group = "chemistry")]
+        concentration: f64,
+        #[approx(group = "chemistry", compare = abs)]
+        ph: f64,
+        pressure: f64,
+    }
+
+    let r1 = Reactor {
+        concentration: 100.0,
+        ph: 7.0,
+        pressure: 1.0,
+    };
+    let r2 = Reactor {
+        concentration: 101.0,
+        ph: 7.05,
+        pressure: 2.0,
+    };
+    assert!(r1.relative_eq_chemistry(&r2, 0.1, 0.02));
+    assert!(!r1.relative_eq_chemistry(&r2, 0.01, 0.02));
+    assert!(!r1.relative_eq_chemistry(&r2, 0.1, 0.001));
+    assert!(r1.abs_diff_eq_chemistry(&r2, 1.0));
+    approx::assert_relative_ne!(r1, r2, epsilon = 0.1, max_relative = 0.02);
+}
+
+#[test]
+fn derive_rel_diff_eq_profiles() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(
+        profile(strict(default_max_relative = 1e-6)),
+        profile(loose(default_epsilon = 1e-4, default_max_relative = 1e-2))
+    )]
+    struct Reactor {
+        concentration: f64,
+        #[approx(profile(loose(static_max_relative = 0.5)))]
+        pressure: f64,
+    }
+
+    let r1 = Reactor {
+        concentration: 100.0,
+        pressure: 1.0,
+    };
+    let r2 = Reactor {
+        concentration: 100.5,
+        pressure: 1.2,
+    };
+    assert!(!r1.relative_eq_profile(&r2, ReactorProfile::Strict));
+    assert!(r1.relative_eq_profile(&r2, ReactorProfile::Loose));
+    assert!(!r1.abs_diff_eq_profile(&r2, ReactorProfile::Loose));
+    approx::assert_relative_ne!(r1, r2);
+}
+
+#[test]
+fn derive_rel_diff_eq_computed_properties() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(also(volume = |c: &Self| c.width * c.height * c.depth, max_relative = 1e-3))]
+    #[approx(mismatches)]
+    struct Cuboid {
+        width: f64,
+        height: f64,
+        depth: f64,
+    }
+
+    let c1 = Cuboid {
+        width: 100.0,
+        height: 100.0,
+        depth: 100.0,
+    };
+    let c2 = Cuboid {
+        width: 100.1,
+        height: 100.1,
+        depth: 100.1,
+    };
+    approx::assert_relative_ne!(c1, c2, epsilon = 0.0, max_relative = 1e-2);
+    let mismatches = c1.approx_relative_mismatches(&c2, 0.0, 1e-2);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "volume");
+    assert_eq!(mismatches[0].max_relative.as_deref(), Some("0.001"));
+    let c3 = Cuboid {
+        width: 100.01,
+        height: 100.0,
+        depth: 100.0,
+    };
+    approx::assert_relative_eq!(c1, c3, epsilon = 0.0, max_relative = 1e-3);
+}
+
+mod interval {
+    #[derive(Clone, Copy, PartialEq, Debug)]
+    pub struct Interval<T> {
+        pub start: T,
+        pub end: T,
+    }
+}
+
+#[test]
+fn derive_rel_diff_eq_remote() {
+    use interval::Interval;
+
+    #[derive(RelativeEq)]
+    #[approx(remote = "interval::Interval")]
+    struct IntervalDef<T> {
+        start: T,
+        end: T,
+    }
+
+    let i1 = Interval {
+        start: 100.0,
+        end: 200.0,
+    };
+    let i2 = Interval {
+        start: 101.0,
+        end: 200.0,
+    };
+    assert!(IntervalDef::relative_eq(&i1, &i2, 0.0, 0.02));
+    assert!(!IntervalDef::relative_eq(&i1, &i2, 0.0, 0.001));
+    assert!(IntervalDef::abs_diff_eq(&i1, &i2, 1.0));
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    struct Schedule {
+        duration: f32,
+        #[approx(with = IntervalDef)]
+        window: Interval<f32>,
+    }
+
+    let s1 = Schedule {
+        duration: 1.0,
+        window: Interval {
+            start: 100.0,
+            end: 200.0,
+        },
+    };
+    let s2 = Schedule {
+        duration: 1.0,
+        window: Interval {
+            start: 101.0,
+            end: 200.0,
+        },
+    };
+    approx::assert_relative_eq!(s1, s2, epsilon = 0.0, max_relative = 0.02);
+    approx::assert_relative_ne!(s1, s2, epsilon = 0.0, max_relative = 0.001);
+}
+
+#[test]
+fn derive_rel_diff_eq_transparent() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(transparent)]
+    struct Wrapper<T>(T);
+
+    approx::assert_relative_eq!(Wrapper(100.0), Wrapper(101.0), max_relative = 0.02);
+    approx::assert_relative_ne!(Wrapper(100.0), Wrapper(101.0), max_relative = 0.001);
+    assert_eq!(
+        <Wrapper<f64> as approx::RelativeEq>::default_max_relative(),
+        f64::EPSILON
+    );
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64)]
+    struct Measurement {
+        #[approx(into_iter)]
+        samples: Vec<Wrapper<f64>>,
+    }
+
+    let m1 = Measurement {
+        samples: vec![Wrapper(1.0), Wrapper(2.0)],
+    };
+    let m2 = Measurement {
+        samples: vec![Wrapper(1.0), Wrapper(2.01)],
+    };
+    approx::assert_relative_eq!(m1, m2, epsilon = 0.0, max_relative = 0.01);
+}
+
+#[test]
+fn derive_rel_diff_eq_via() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(via = f64, convert = |d: &Self| match d {
+        Distance::Meters(m) => *m,
+        Distance::Kilometers(km) => 1000.0 * km,
+    })]
+    enum Distance {
+        Meters(f64),
+        Kilometers(f64),
+    }
+
+    approx::assert_relative_eq!(Distance::Kilometers(1.0), Distance::Meters(1000.0));
+    approx::assert_relative_eq!(
+        Distance::Kilometers(1.0),
+        Distance::Meters(1005.0),
+        max_relative = 0.01
+    );
+    approx::assert_relative_ne!(
+        Distance::Kilometers(1.0),
+        Distance::Meters(1005.0),
+        max_relative = 0.001
+    );
+}
+
+#[test]
+fn derive_rel_diff_eq_cast_conversions() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    struct Mixed {
+        coarse: f32,
+        #[approx(cast_value = into)]
+        fine: f64,
+    }
+
+    let m1 = Mixed {
+        coarse: 1.0,
+        fine: 100.0,
+    };
+    let m2 = Mixed {
+        coarse: 1.0,
+        fine: 101.0,
+    };
+    approx::assert_relative_ne!(m1, m2, max_relative = 0.005);
+    approx::assert_relative_eq!(m1, m2, max_relative = 0.02);
+
+    // Conversions which fail report the unconverted values
+    #[derive(Clone, PartialEq, Debug)]
+    struct Raw(u8);
+
+    impl TryFrom<Raw> for f64 {
+        type Error = ();
+
+        fn try_from(raw: Raw) -> Result<Self, Self::Error> {
+            match raw.0 {
+                u8::MAX => Err(()),
+                value => Ok(value as f64),
+            }
+        }
+    }
+
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(mismatches)]
+    struct Channel {
+        gain: f64,
+        #[approx(cast_field(by = try_into))]
+        raw: Raw,
+    }
+
+    let c1 = Channel {
+        gain: 1.0,
+        raw: Raw(10),
+    };
+    let c2 = Channel {
+        gain: 1.0,
+        raw: Raw(u8::MAX),
+    };
+    approx::assert_relative_eq!(c1, c1);
+    approx::assert_relative_ne!(c1, c2, max_relative = 1.0);
+    let mismatches = c1.approx_relative_mismatches(&c2, 0.0, 1.0);
+    assert_eq!(mismatches.len(), 1);
+    assert_eq!(mismatches[0].path, "raw");
+    assert_eq!(mismatches[0].mode, "cast");
+    assert_eq!(mismatches[0].left, "Raw(10)");
+    assert_eq!(mismatches[0].right, "Raw(255)");
+    assert_eq!(mismatches[0].diff, None);
+}
@@ -0,0 +1,344 @@
+#[cfg(feature = "infer_name")]
+use approxim as approx;
+
+use approx_derive::*;
+
+#[test]
+fn derive_ulps_eq() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct MyStruct {
+        value: f64,
+    }
+
+    let s1 = MyStruct { value: 1.0 };
+    let s2 = MyStruct {
+        value: 1.0 + 2.0 * f64::EPSILON,
+    };
+    approx::assert_ulps_eq!(s1, s2);
+    approx::assert_ulps_ne!(s1, s2, max_ulps = 1);
+}
+
+#[test]
+fn derive_ulps_eq_relative() {
+    #[derive(RelativeEq, UlpsEq, PartialEq, Debug)]
+    struct Position(f32, f32);
+
+    let p1 = Position(1.0, 2.0);
+    let p2 = Position(1.0, 2.0 + 2.0 * f32::EPSILON);
+    approx::assert_relative_eq!(p1, p2);
+    approx::assert_ulps_eq!(p1, p2);
+}
+
+#[test]
+fn derive_ulps_eq_default_max_ulps() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    #[approx(default_max_ulps = 0)]
+    struct MyStruct {
+        value: f64,
+    }
+
+    let s1 = MyStruct { value: 1.0 };
+    let s2 = MyStruct {
+        value: 1.0 + f64::EPSILON,
+    };
+    approx::assert_ulps_ne!(s1, s2, epsilon = 0.0);
+    approx::assert_ulps_eq!(s1, s2, epsilon = 0.0, max_ulps = 1);
+}
+
+#[test]
+fn derive_ulps_eq_static_max_ulps() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct MyStruct {
+        v1: f64,
+        #[approx(static_max_ulps = 10)]
+        v2: f64,
+    }
+
+    let s1 = MyStruct { v1: 1.0, v2: 1.0 };
+    let s2 = MyStruct {
+        v1: 1.0,
+        v2: 1.0 + 8.0 * f64::EPSILON,
+    };
+    approx::assert_ulps_eq!(s1, s2, max_ulps = 0);
+}
+
+#[test]
+fn derive_ulps_eq_max_ulps_map() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct MyStruct {
+        v1: f64,
+        #[approx(max_ulps_map = |x| 2 * x)]
+        v2: f64,
+    }
+
+    let s1 = MyStruct { v1: 1.0, v2: 1.0 };
+    let s2 = MyStruct {
+        v1: 1.0 + 2.0 * f64::EPSILON,
+        v2: 1.0 + 4.0 * f64::EPSILON,
+    };
+    approx::assert_ulps_eq!(s1, s2, max_ulps = 2);
+    approx::assert_ulps_ne!(s1, s2, max_ulps = 1);
+}
+
+#[test]
+fn derive_ulps_eq_cast_field() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct MyStruct {
+        v1: f32,
+        #[approx(cast_field)]
+        v2: f64,
+    }
+
+    let s1 = MyStruct { v1: 1.0, v2: 3.0 };
+    let s2 = MyStruct {
+        v1: 1.0,
+        v2: 3.0 + f64::MIN_POSITIVE,
+    };
+    approx::assert_ulps_eq!(s1, s2, max_ulps = 0);
+}
+
+#[test]
+fn derive_ulps_eq_map() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct Car {
+        max_speed: f32,
+        #[approx(map = |x| x)]
+        battery: Option<f32>,
+    }
+
+    let c1 = Car {
+        max_speed: 180.0,
+        battery: Some(1.0),
+    };
+    let c2 = Car {
+        max_speed: 180.0,
+        battery: None,
+    };
+    approx::assert_ulps_eq!(c1, c1);
+    approx::assert_ulps_ne!(c1, c2);
+}
+
+#[test]
+fn derive_ulps_eq_into_iter() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64)]
+    struct Polynomial {
+        #[approx(into_iter)]
+        coefficients: Vec<f64>,
+    }
+
+    let p1 = Polynomial {
+        coefficients: vec![1.0, 0.5],
+    };
+    let p2 = Polynomial {
+        coefficients: vec![1.0, 0.5 + f64::EPSILON],
+    };
+    let p3 = Polynomial {
+        coefficients: vec![1.0, 0.5, 0.25],
+    };
+    approx::assert_ulps_eq!(p1, p2);
+    approx::assert_ulps_ne!(p1, p3);
+}
+
+#[test]
+fn derive_ulps_eq_enum() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    enum Parameter {
+        Fixed(f32),
+        Range {
+            lower: f32,
+            #[approx(static_max_ulps = 0)]
+            upper: f32,
+        },
+    }
+
+    let p1 = Parameter::Fixed(1.0);
+    let p2 = Parameter::Fixed(1.0 + f32::EPSILON);
+    let p3 = Parameter::Range {
+        lower: 1.0,
+        upper: 2.0,
+    };
+    let p4 = Parameter::Range {
+        lower: 1.0,
+        upper: 2.0 + 2.0 * f32::EPSILON,
+    };
+    approx::assert_ulps_eq!(p1, p2);
+    approx::assert_ulps_ne!(p1, p3);
+    approx::assert_ulps_ne!(p3, p4);
+}
+
+#[test]
+fn derive_ulps_eq_compare() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct Counter {
+        #[approx(compare = abs)]
+        total: f32,
+        mean: f32,
+    }
+
+    let c1 = Counter {
+        total: 1e10,
+        mean: 1e10,
+    };
+    let c2 = Counter {
+        total: 1e10,
+        mean: 1e10 + 1024.0,
+    };
+    let c3 = Counter {
+        total: 1e10 + 1024.0,
+        mean: 1e10,
+    };
+    approx::assert_ulps_eq!(c1, c2);
+    approx::assert_ulps_ne!(c1, c3);
+}
+
+#[test]
+fn derive_ulps_eq_groups() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct Vector {
+        #[approx(group = "planar")]
+        x: f32,
+        #[approx(group = "planar")]
+        y: f32,
+        z: f32,
+    }
+
+    let v1 = Vector {
+        x: 1.0,
+        y: 2.0,
+        z: 3.0,
+    };
+    let v2 = Vector {
+        x: 1.0 + f32::EPSILON,
+        y: 2.0,
+        z: 4.0,
+    };
+    assert!(v1.ulps_eq_planar(&v2, 0.0, 1));
+    assert!(!v1.ulps_eq_planar(&v2, 0.0, 0));
+    approx::assert_ulps_ne!(v1, v2);
+}
+
+#[test]
+fn derive_ulps_eq_profiles() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    #[approx(
+        profile(strict(default_epsilon = 0.0, default_max_ulps = 0)),
+        profile(loose(default_epsilon = 0.0, default_max_ulps = 4))
+    )]
+    struct Vector {
+        x: f32,
+        #[approx(profile(strict(static_max_ulps = 2)))]
+        y: f32,
+    }
+
+    let v1 = Vector { x: 1.0, y: 2.0 };
+    let v2 = Vector {
+        x: 1.0 + f32::EPSILON,
+        y: 2.0,
+    };
+    let v3 = Vector {
+        x: 1.0,
+        y: 2.0 + 2.0 * f32::EPSILON,
+    };
+    assert!(!v1.ulps_eq_profile(&v2, VectorProfile::Strict));
+    assert!(v1.ulps_eq_profile(&v2, VectorProfile::Loose));
+    assert!(v1.ulps_eq_profile(&v3, VectorProfile::Strict));
+    approx::assert_ulps_ne!(v1, v2, epsilon = 0.0, max_ulps = 0);
+}
+
+#[test]
+fn derive_ulps_eq_computed_properties() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    #[approx(also(sum = |v: &Self| v.x + v.y, max_ulps = 0))]
+    struct Vector {
+        x: f32,
+        y: f32,
+    }
+
+    let v1 = Vector { x: 1.0, y: 2.0 };
+    let v2 = Vector {
+        x: 1.0 + f32::EPSILON,
+        y: 2.0,
+    };
+    approx::assert_ulps_eq!(v1, v2, epsilon = 0.0, max_ulps = 1);
+    let v3 = Vector {
+        x: 1.0 + 4.0 * f32::EPSILON,
+        y: 2.0,
+    };
+    approx::assert_ulps_ne!(v1, v3, epsilon = 0.0, max_ulps = 4);
+}
+
+#[test]
+fn derive_ulps_eq_transparent() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    #[approx(transparent)]
+    struct Seconds {
+        value: f32,
+    }
+
+    let s1 = Seconds { value: 1.0 };
+    let s2 = Seconds {
+        value: 1.0 + 2.0 * f32::EPSILON,
+    };
+    approx::assert_ulps_eq!(s1, s2, epsilon = 0.0, max_ulps = 2);
+    approx::assert_ulps_ne!(s1, s2, epsilon = 0.0, max_ulps = 1);
+    assert_eq!(<Seconds as approx::UlpsEq>::default_max_ulps(), 4);
+}
+
+#[test]
+fn derive_ulps_eq_via() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    #[approx(via = f32, convert = Fraction::value)]
+    struct Fraction {
+        numerator: f32,
+        denominator: f32,
+    }
+
+    impl Fraction {
+        fn value(&self) -> f32 {
+            self.numerator / self.denominator
+        }
+    }
+
+    let f1 = Fraction {
+        numerator: 1.0,
+        denominator: 2.0,
+    };
+    let f2 = Fraction {
+        numerator: 2.0,
+        denominator: 4.0,
+    };
+    approx::assert_ulps_eq!(f1, f2, epsilon = 0.0, max_ulps = 0);
+    approx::assert_ulps_ne!(
+        f1,
+        Fraction {
+            numerator: 1.0,
+            denominator: 3.0
+        }
+    );
+}
+
+#[test]
+fn derive_ulps_eq_cast_conversions() {
+    #[derive(AbsDiffEq, UlpsEq, PartialEq, Debug)]
+    struct MyStruct {
+        v1: f64,
+        #[approx(cast_field = into)]
+        v2: f32,
+        #[approx(cast_field(to = f64, by = try_into))]
+        v3: i32,
+    }
+
+    let s1 = MyStruct {
+        v1: 1.0,
+        v2: 3.0,
+        v3: 4,
+    };
+    let s2 = MyStruct {
+        v1: 1.0,
+        v2: 3.0 + f32::EPSILON * 3.0,
+        v3: 4,
+    };
+    approx::assert_ulps_ne!(s1, s2, max_ulps = 0);
+    approx::assert_ulps_eq!(s1, s2, max_ulps = 1 << 30);
+}
@@ -0,0 +1,75 @@
+#![no_std]
+#[cfg(feature = "infer_name")]
+use approxim as approx;
+
+use approx_derive::*;
+
+#[test]
+fn derive_abs_diff_eq_no_std() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    struct Reading {
+        value: f32,
+        #[approx(equal)]
+        channel: u8,
+    }
+
+    let r1 = Reading {
+        value: 1.0,
+        channel: 0,
+    };
+    let r2 = Reading {
+        value: 1.05,
+        channel: 0,
+    };
+    approx::assert_abs_diff_eq!(r1, r2, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(r1, r2, epsilon = 0.01);
+}
+
+#[test]
+fn derive_rel_diff_eq_mismatches_no_std() {
+    #[derive(RelativeEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct Sample {
+        #[approx(into_iter)]
+        values: [f64; 2],
+        #[approx(equal)]
+        id: usize,
+    }
+
+    let s1 = Sample {
+        values: [1.0, 2.0],
+        id: 1,
+    };
+    let s2 = Sample {
+        values: [2.0, 1.5],
+        id: 2,
+    };
+    let mismatches = s1.approx_relative_mismatches(&s2, 0.1, 0.1);
+    assert_eq!(mismatches.len(), 3);
+    assert_eq!(mismatches[0].path, "values[0]");
+    assert_eq!(mismatches[2].path, "id");
+    let mismatches: &[SampleMismatch] = &s1.approx_mismatches(&s2, 0.1);
+    assert_eq!(mismatches[2].left, "1");
+}
+
+#[test]
+fn derive_abs_diff_eq_unordered_no_std() {
+    #[derive(AbsDiffEq, PartialEq, Debug)]
+    #[approx(epsilon_type = f64, mismatches)]
+    struct Peaks {
+        #[approx(into_iter, unordered)]
+        positions: [f64; 3],
+    }
+
+    let p1 = Peaks {
+        positions: [1.0, 2.0, 3.0],
+    };
+    let p2 = Peaks {
+        positions: [3.05, 1.0, 2.0],
+    };
+    approx::assert_abs_diff_eq!(p1, p2, epsilon = 0.1);
+    approx::assert_abs_diff_ne!(p1, p2, epsilon = 0.01);
+    let mismatches = p1.approx_mismatches(&p2, 0.01);
+    assert_eq!(mismatches.len(), 2);
+    assert_eq!(mismatches[0].right, "<unmatched>");
+}